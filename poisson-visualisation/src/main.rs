@@ -1,11 +1,14 @@
 use std::hash::Hasher;
 
-use clap::{App, Arg, ArgMatches, arg_enum, value_t};
+use clap::{arg_enum, value_t, App, Arg, ArgMatches};
 use fnv::FnvHasher;
 use image::{ImageBuffer, Rgb};
 use lab::Lab;
-use poisson2d::{Builder, Type, algorithm::{Bridson, Ebeida}};
-use rand::{rngs::SmallRng, Rng, seq::SliceRandom, SeedableRng};
+use poisson2d::{
+    algorithm::{AnyAlgorithm, Bridson, Ebeida},
+    Builder, Type,
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
 
 arg_enum! {
     #[derive(PartialEq, Debug)]
@@ -92,14 +95,15 @@ fn visualise(m: ArgMatches) {
 
     let mut style_rng = master_rng.clone();
 
-    let builder = Builder::with_radius(radius, Type::Normal);
-    let points = if algo == Algo::Ebeida {
-        builder.build(master_rng, Ebeida).generate()
-    } else {
-        builder.build(master_rng, Bridson).generate()
+    let algo = match algo {
+        Algo::Ebeida => AnyAlgorithm::from(Ebeida),
+        Algo::Bridson => AnyAlgorithm::from(Bridson),
     };
+    let points = Builder::with_radius(radius, Type::Normal)
+        .build(master_rng, algo)
+        .generate();
 
-    let mut ps = points.clone();
+    let mut ps = points.to_vec();
     ps.shuffle(&mut style_rng);
 
     let mut image = ImageBuffer::new(width, height);
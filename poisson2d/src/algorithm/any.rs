@@ -0,0 +1,120 @@
+use rand::Rng;
+
+use crate::algorithm::{bridson, ebeida, elimination};
+use crate::algorithm::{
+    Algorithm, Bridson, BridsonConfig, Creator, Ebeida, EbeidaConfig, SampleElimination,
+};
+use crate::Builder;
+
+/// Algorithm chosen while the program runs, such as from a configuration file or user interface, which generates the
+/// same samples as the algorithm it wraps.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnyAlgorithm {
+    /// Ebeida algorithm with the configuration given.
+    Ebeida(EbeidaConfig),
+    /// Bridson algorithm with the configuration given.
+    Bridson(BridsonConfig),
+    /// Sample elimination algorithm.
+    SampleElimination,
+}
+
+impl Creator for AnyAlgorithm {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        match *self {
+            AnyAlgorithm::Ebeida(config) => Algo::Ebeida(config.create(poisson)),
+            AnyAlgorithm::Bridson(config) => Algo::Bridson(config.create(poisson)),
+            AnyAlgorithm::SampleElimination => {
+                Algo::SampleElimination(SampleElimination.create(poisson))
+            }
+        }
+    }
+}
+
+impl From<Ebeida> for AnyAlgorithm {
+    fn from(_: Ebeida) -> Self {
+        AnyAlgorithm::Ebeida(EbeidaConfig::new())
+    }
+}
+
+impl From<EbeidaConfig> for AnyAlgorithm {
+    fn from(config: EbeidaConfig) -> Self {
+        AnyAlgorithm::Ebeida(config)
+    }
+}
+
+impl From<Bridson> for AnyAlgorithm {
+    fn from(_: Bridson) -> Self {
+        AnyAlgorithm::Bridson(BridsonConfig::new())
+    }
+}
+
+impl From<BridsonConfig> for AnyAlgorithm {
+    fn from(config: BridsonConfig) -> Self {
+        AnyAlgorithm::Bridson(config)
+    }
+}
+
+impl From<SampleElimination> for AnyAlgorithm {
+    fn from(_: SampleElimination) -> Self {
+        AnyAlgorithm::SampleElimination
+    }
+}
+
+/// Implementation for the algorithm chosen while the program runs
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Algo {
+    /// Implementation for the Ebeida algorithm.
+    Ebeida(ebeida::Algo),
+    /// Implementation for the Bridson algorithm.
+    Bridson(bridson::Algo),
+    /// Implementation for the sample elimination algorithm.
+    SampleElimination(elimination::Algo),
+}
+
+impl Algorithm for Algo {
+    fn next<R>(&mut self, poisson: &mut Builder, rng: &mut R) -> Option<mint::Vector2<f32>>
+    where
+        R: Rng,
+    {
+        match self {
+            Algo::Ebeida(algo) => algo.next(poisson, rng),
+            Algo::Bridson(algo) => algo.next(poisson, rng),
+            Algo::SampleElimination(algo) => algo.next(poisson, rng),
+        }
+    }
+
+    fn size_hint(&self, poisson: &Builder) -> (usize, Option<usize>) {
+        match self {
+            Algo::Ebeida(algo) => algo.size_hint(poisson),
+            Algo::Bridson(algo) => algo.size_hint(poisson),
+            Algo::SampleElimination(algo) => algo.size_hint(poisson),
+        }
+    }
+
+    fn restrict(&mut self, sample: mint::Vector2<f32>) {
+        match self {
+            Algo::Ebeida(algo) => algo.restrict(sample),
+            Algo::Bridson(algo) => algo.restrict(sample),
+            Algo::SampleElimination(algo) => algo.restrict(sample),
+        }
+    }
+
+    fn seed(&mut self, sample: mint::Vector2<f32>) {
+        match self {
+            Algo::Ebeida(algo) => algo.seed(sample),
+            Algo::Bridson(algo) => algo.seed(sample),
+            Algo::SampleElimination(algo) => algo.seed(sample),
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder, sample: mint::Vector2<f32>) -> bool {
+        match self {
+            Algo::Ebeida(algo) => algo.stays_legal(poisson, sample),
+            Algo::Bridson(algo) => algo.stays_legal(poisson, sample),
+            Algo::SampleElimination(algo) => algo.stays_legal(poisson, sample),
+        }
+    }
+}
@@ -4,11 +4,13 @@ use rand::Rng;
 use rand_distr::StandardNormal;
 use sphere::sphere_volume;
 
+use crate::algorithm::ebeida::{self, EbeidaConfig};
 use crate::algorithm::{Algorithm, Creator};
 use crate::utils::*;
-use crate::Builder;
+use crate::{Builder, Error};
 
 /// Generates approximately uniform non-maximal Poisson disk samplings with O(n) time and O(n) space complexity relative to the number of samples generated.
+/// The samplings can be made maximal with `BridsonConfig::with_fill_gaps`.
 /// Based on Bridson, Robert. "Fast Poisson disk sampling in arbitrary dimensions." SIGGRAPH Sketches. 2007.
 #[derive(Debug, Clone, Copy)]
 pub struct Bridson;
@@ -16,22 +18,119 @@ pub struct Bridson;
 impl Creator for Bridson {
     type Algo = Algo;
 
-    fn create(poisson: &Builder) -> Self::Algo {
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        BridsonConfig::default().create(poisson)
+    }
+}
+
+/// Relative distance beyond the smallest one allowed at which Roberts' candidates are placed, so that rounding
+/// doesn't push them too close to the sample they were thrown around.
+const ROBERTS_MARGIN: f32 = 1e-3;
+
+/// Ways of choosing the candidates thrown around an active sample.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Candidates {
+    /// Uniform distance and direction in the annulus between two and four times the radius.
+    /// Candidates cluster toward the inner circle of the annulus.
+    Radial,
+    /// Uniform position in the annulus between two and four times the radius.
+    Uniform,
+    /// Evenly spaced directions starting from a random one, right beyond the inner circle of the annulus.
+    /// Based on Roberts, Martin. "An improved version of Bridson's algorithm for Poisson disc sampling." 2019.
+    Roberts,
+}
+
+/// Configuration of the Bridson algorithm, which is used in place of `Bridson` to change how it samples.
+/// Samplings on the sphere don't support it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BridsonConfig {
+    candidate_count: usize,
+    candidates: Candidates,
+    fill_gaps: bool,
+}
+
+impl Default for BridsonConfig {
+    fn default() -> Self {
+        BridsonConfig {
+            candidate_count: 30,
+            candidates: Candidates::Radial,
+            fill_gaps: false,
+        }
+    }
+}
+
+impl BridsonConfig {
+    /// New configuration which samples the same way as `Bridson`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the amount of candidates thrown around an active sample before it's deactivated, 30 by default.
+    ///
+    /// Fewer candidates finish sooner, but leave more room between the samples.
+    pub fn with_candidate_count(self, candidate_count: usize) -> Self {
+        self.try_with_candidate_count(candidate_count)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Sets the amount of candidates thrown around an active sample before it's deactivated.
+    /// Fails if the amount is 0.
+    pub fn try_with_candidate_count(mut self, candidate_count: usize) -> Result<Self, Error> {
+        if candidate_count == 0 {
+            return Err(Error::ZeroCandidates);
+        }
+        self.candidate_count = candidate_count;
+        Ok(self)
+    }
+
+    /// Sets how the candidates around an active sample are chosen, `Candidates::Radial` by default.
+    pub fn with_candidates(mut self, candidates: Candidates) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Sets whether the gaps left once the sampling stops growing are filled, which makes the sampling maximal.
+    ///
+    /// The gaps are found and filled the same way as the exact Ebeida algorithm does, which keeps the generation
+    /// O(n) but samples the gaps less evenly than the rest of the sampling.
+    pub fn with_fill_gaps(mut self, fill_gaps: bool) -> Self {
+        self.fill_gaps = fill_gaps;
+        self
+    }
+}
+
+impl Creator for BridsonConfig {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
         Algo {
-            grid: Grid::new(poisson.radius, poisson.poisson_type),
+            grid: Grid::new(poisson),
             active_samples: vec![],
             outside: vec![],
             success: 0,
+            seeded: false,
+            candidate_count: self.candidate_count,
+            candidates: self.candidates,
+            fill_gaps: self.fill_gaps,
+            gaps: None,
         }
     }
 }
 
 /// Implementation for the Bridson algorithm
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Algo {
     grid: Grid,
     active_samples: Vec<Vec2>,
     outside: Vec<Vec2>,
     success: usize,
+    seeded: bool,
+    candidate_count: usize,
+    candidates: Candidates,
+    fill_gaps: bool,
+    gaps: Option<ebeida::Algo>,
 }
 
 impl Algorithm for Algo {
@@ -39,92 +138,153 @@ impl Algorithm for Algo {
     where
         R: Rng,
     {
+        if let Some(ref mut gaps) = self.gaps {
+            return gaps.next(poisson, rng);
+        }
         while !self.active_samples.is_empty() {
             let index = rng.sample(Uniform::new(0, self.active_samples.len()));
-            let cur = self.active_samples[index].clone();
-            for _ in 0..30 {
-                let min = 2.0 * poisson.radius;
-                let max = 4.0 * poisson.radius;
-                let sample = cur.clone() + random_point_annulus(rng, min, max).into();
-                if (0..2)
-                    .map(|n| sample[n])
-                    .all(|c| 0.0 <= c && c < 1.0)
-                {
-                    let index = sample_to_index(&sample, self.grid.side());
-                    if self.insert_if_valid(poisson, index, sample.clone()) {
+            let cur = self.active_samples[index];
+            let radius = poisson.radius_at(cur.into());
+            let min = 2.0 * radius;
+            let max = 4.0 * radius;
+            // Only Roberts' candidates need a random starting direction, which keeps the others reproducible.
+            let start = match self.candidates {
+                Candidates::Roberts => rng.gen::<f32>(),
+                _ => 0.0,
+            };
+            for i in 0..self.candidate_count {
+                let offset = match self.candidates {
+                    Candidates::Radial => random_point_annulus(rng, min, max),
+                    Candidates::Uniform => random_point_annulus_uniform(rng, min, max),
+                    Candidates::Roberts => {
+                        let angle = 2.0
+                            * std::f32::consts::PI
+                            * (start + i as f32 / self.candidate_count as f32);
+                        Vec2::new(angle.cos(), angle.sin()) * min * (1.0 + ROBERTS_MARGIN)
+                    }
+                };
+                let sample = cur + offset;
+                if poisson.contains(sample) {
+                    let index = self.index_of(sample);
+                    if self.insert_if_valid(poisson, index, sample) {
                         return Some(sample.into());
                     }
                 }
             }
             self.active_samples.swap_remove(index);
         }
-        while self.success == 0 {
-            let cell = rng.sample(Uniform::new(0, self.grid.cells()));
-            let index: Vec2 = decode(cell, self.grid.side()).expect(
-                "Because we are decoding random index within grid \
-                 this should work.",
-            );
-            let sample = choose_random_sample(rng, &self.grid, index.clone(), 0);
-            if self.insert_if_valid(poisson, index, sample.clone()) {
-                return Some(sample.into());
+        if !self.seeded {
+            self.seeded = true;
+            let spacing = self.grid.cell();
+            let cells = (0..self.grid.cells())
+                .map(|cell| {
+                    decode(cell, self.grid.side())
+                        .expect("Because we are decoding index within grid this should work.")
+                })
+                .filter(|&index| {
+                    let (min, max) = cell_bounds(index, spacing);
+                    poisson.overlaps(min, max)
+                })
+                .collect::<Vec<_>>();
+            // Restricted samples can cover every cell, so give up after enough failed throws.
+            for _ in 0..30 * cells.len() {
+                let index = cells[rng.sample(Uniform::new(0, cells.len()))];
+                let sample = choose_random_sample(rng, &self.grid, index, 0);
+                if poisson.contains(sample) && self.insert_if_valid(poisson, index, sample) {
+                    return Some(sample.into());
+                }
             }
         }
+        if self.fill_gaps {
+            // The gaps take over the grid and the samples outside of it, which stay with them from now on.
+            let gaps = EbeidaConfig::new().with_exact(true).fill(
+                poisson,
+                std::mem::take(&mut self.grid),
+                std::mem::take(&mut self.outside),
+                self.success,
+            );
+            return self.gaps.insert(gaps).next(poisson, rng);
+        }
         None
     }
 
     fn size_hint(&self, poisson: &Builder) -> (usize, Option<usize>) {
+        if let Some(ref gaps) = self.gaps {
+            return gaps.size_hint(poisson);
+        }
         // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
-        let upper = if self.grid.cells() > self.success {
-            self.grid.cells() - self.success
-        } else {
-            0
-        };
+        let upper = self.grid.cells().saturating_sub(self.success);
+        if self.fill_gaps {
+            // Disks of twice the largest radius around the samples of a maximal sampling cover the whole area, which
+            // takes at least this many of them. The area left by a polygon or predicate isn't known, so it gives no
+            // lower bound.
+            let lower = if poisson.is_masked() {
+                0
+            } else {
+                let disks = poisson.dimensions.x() * poisson.dimensions.y()
+                    / sphere_volume(2.0 * poisson.max_radius(), 2);
+                (disks.floor() as usize).saturating_sub(self.success + 1)
+            };
+            return (lower, Some(upper));
+        }
         // Calculating lower bound should work because we calculate how much volume is left to be filled at worst case and
         // how much sphere can fill it at best case and just figure out how many fills are still needed.
         let spacing = self.grid.cell();
-        let grid_volume = (upper as f32) * spacing.powi(2);
-        let sphere_volume = sphere_volume(2.0 * poisson.radius, 2);
+        let grid_volume = (upper as f32) * spacing.x() * spacing.y();
+        let sphere_volume = sphere_volume(2.0 * poisson.max_radius(), 2);
         let lower: f32 = grid_volume / sphere_volume;
-        let mut lower = lower.floor() as usize;
-        if lower > 0 {
-            lower -= 1;
-        }
+        let lower = (lower.floor() as usize).saturating_sub(1);
         (lower, Some(upper))
     }
 
     fn restrict(&mut self, sample: mint::Vector2<f32>) {
+        if let Some(ref mut gaps) = self.gaps {
+            gaps.restrict(sample);
+            return;
+        }
         let sample: Vec2 = sample.into();
         self.success += 1;
-        let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
+        let index = sample_to_index(&sample, self.grid.cell());
+        if let Err(sample) = self.grid.insert(index, sample) {
             self.outside.push(sample);
         }
     }
 
+    fn seed(&mut self, sample: mint::Vector2<f32>) {
+        self.restrict(sample);
+        if self.gaps.is_some() {
+            // The sampling doesn't grow anymore once the gaps are filled.
+            return;
+        }
+        self.active_samples.push(sample.into());
+        // The sampling only grows from the seeds given, so no random sample is thrown to start it.
+        self.seeded = true;
+    }
+
     fn stays_legal(&self, poisson: &Builder, sample: mint::Vector2<f32>) -> bool {
+        if let Some(ref gaps) = self.gaps {
+            return gaps.stays_legal(poisson, sample);
+        }
         let sample: Vec2 = sample.into();
-        let index = sample_to_index(&sample, self.grid.side());
-        is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
+        let index = sample_to_index(&sample, self.grid.cell());
+        is_disk_free(&self.grid, poisson, index, 0, sample, &self.outside)
     }
 }
 
 impl Algo {
-    fn insert_if_valid(&mut self, poisson: &mut Builder, index: Vec2, sample: Vec2) -> bool {
-        if is_disk_free(
-            &self.grid,
-            poisson,
-            index.clone(),
-            0,
-            sample.clone(),
-            &self.outside,
-        ) {
-            self.active_samples.push(sample.clone());
+    fn index_of(&self, sample: Vec2) -> [i32; 2] {
+        // Rounding can push samples right at the far edges into a cell that doesn't exist.
+        let side = self.grid.side();
+        let [x, y] = sample_to_index(&sample, self.grid.cell());
+        [x.min(side[0] as i32 - 1), y.min(side[1] as i32 - 1)]
+    }
+
+    fn insert_if_valid(&mut self, poisson: &mut Builder, index: [i32; 2], sample: Vec2) -> bool {
+        if is_disk_free(&self.grid, poisson, index, 0, sample, &self.outside) {
+            self.active_samples.push(sample);
             self.grid
-                .get_mut(index)
-                .expect("Because the sample is in [0, 1), indexing it should work.")
-                .push(sample);
+                .insert(index, sample)
+                .expect("Because the sample is in the domain, indexing it should work.");
             self.success += 1;
             true
         } else {
@@ -149,6 +309,36 @@ where
     }
 }
 
+/// Chooses random point of the annulus with all points being equally likely.
+fn random_point_annulus_uniform<R>(rand: &mut R, min: f32, max: f32) -> Vec2
+where
+    R: Rng,
+{
+    let angle = rand.gen::<f32>() * 2.0 * std::f32::consts::PI;
+    let sqmin = min * min;
+    let radius = (sqmin + rand.gen::<f32>() * (max * max - sqmin)).sqrt();
+    Vec2::new(angle.cos(), angle.sin()) * radius.max(min).min(max)
+}
+
+#[test]
+fn random_point_annulus_uniform_covers_area_evenly() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let mut rng = SmallRng::seed_from_u64(42);
+    let (mut inner, mut outer) = (0, 0);
+    for _ in 0..10000 {
+        let result = random_point_annulus_uniform(&mut rng, 1.0, 2.0);
+        let length = result.length();
+        assert!((1.0 - 1e-6..=2.0 + 1e-6).contains(&length));
+        // Circle of radius sqrt(2.5) splits the annulus into halves of equal area.
+        if length < 2.5f32.sqrt() {
+            inner += 1;
+        } else {
+            outer += 1;
+        }
+    }
+    assert!((inner as f32 / outer as f32 - 1.0).abs() < 0.1);
+}
+
 #[test]
 fn random_point_annulus_does_not_generate_outside_annulus() {
     use rand::{rngs::SmallRng, SeedableRng};
@@ -4,8 +4,12 @@ use rand::Rng;
 use sphere::sphere_volume;
 
 use crate::algorithm::{Algorithm, Creator};
+use crate::utils::void::{self, Point};
 use crate::utils::*;
-use crate::Builder;
+use crate::{Builder, Error, Type};
+
+/// Level of subdivision at which exact Ebeida stops subdividing the cells and starts filling the gaps with polygons.
+const VOID_LEVEL: usize = 8;
 
 /// Generates uniform maximal Poisson disk samplings with O(n2<sup>d</sup>) time and O(n2<sup>d</sup>) space complexity relative to the number of samples generated and the dimensionality of the sampling volume.
 /// Based on Ebeida, Mohamed S., et al. "A Simple Algorithm for Maximal Poisson‐Disk Sampling in High Dimensions." Computer Graphics Forum. Vol. 31. No. 2pt4. Blackwell Publishing Ltd, 2012.
@@ -15,37 +19,181 @@ pub struct Ebeida;
 impl Creator for Ebeida {
     type Algo = Algo;
 
-    fn create(poisson: &Builder) -> Self::Algo {
-        let grid = Grid::new(poisson.radius, poisson.poisson_type);
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        EbeidaConfig::default().create(poisson)
+    }
+}
+
+/// Configuration of the Ebeida algorithm, which is used in place of `Ebeida` to change how it samples.
+/// Samplings on the sphere don't support it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EbeidaConfig {
+    exact: bool,
+    throw_factor: f64,
+    max_level: usize,
+}
+
+impl Default for EbeidaConfig {
+    fn default() -> Self {
+        EbeidaConfig {
+            exact: false,
+            throw_factor: 0.3,
+            max_level: f32::MANTISSA_DIGITS as usize,
+        }
+    }
+}
+
+impl EbeidaConfig {
+    /// New configuration which samples the same way as `Ebeida`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many samples are thrown at each level of subdivision relative to the amount of active cells, 0.3 by
+    /// default.
+    ///
+    /// More throws leave fewer cells to subdivide, but waste more of them on cells which are almost covered.
+    pub fn with_throw_factor(self, throw_factor: f64) -> Self {
+        self.try_with_throw_factor(throw_factor)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Sets how many samples are thrown at each level of subdivision relative to the amount of active cells.
+    /// Fails if the throw factor isn't finite and larger than 0.
+    pub fn try_with_throw_factor(mut self, throw_factor: f64) -> Result<Self, Error> {
+        if !(throw_factor > 0.0 && throw_factor.is_finite()) {
+            return Err(Error::InvalidThrowFactor(throw_factor));
+        }
+        self.throw_factor = throw_factor;
+        Ok(self)
+    }
+
+    /// Sets the amount of times the cells are subdivided at most, `f32::MANTISSA_DIGITS` by default.
+    /// The amount should be at least 1.
+    ///
+    /// Fewer levels finish sooner, but leave larger gaps unless the sampling is exact.
+    /// The amount is lowered to what the precision of `f32` and the `i32` indices of the cells allow, which is
+    /// `31 - b` levels for grids with `b` bits in the amount of cells along their longer side.
+    /// Grids with 128 or more cells along a side therefore get fewer than `f32::MANTISSA_DIGITS` levels, which
+    /// `Algo::max_level` reports.
+    pub fn with_max_level(self, max_level: usize) -> Self {
+        self.try_with_max_level(max_level)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Sets the amount of times the cells are subdivided at most.
+    /// Fails if the amount is 0.
+    pub fn try_with_max_level(mut self, max_level: usize) -> Result<Self, Error> {
+        if max_level == 0 {
+            return Err(Error::ZeroMaxLevel);
+        }
+        self.max_level = max_level;
+        Ok(self)
+    }
+
+    /// Sets whether the gaps left between the samples are found exactly.
+    ///
+    /// Exact sampling subdivides the cells only a few times and then clips the parts of them no disk covers into
+    /// polygons and samples inside of them until no gaps remain, which guarantees a maximal sampling.
+    /// Otherwise the cells are subdivided until the precision of `f32` runs out, which can leave tiny gaps.
+    pub fn with_exact(mut self, exact: bool) -> Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Creates the algorithm to fill the gaps left around the samples already in the grid or outside of it.
+    pub(crate) fn fill(
+        &self,
+        poisson: &Builder,
+        grid: Grid,
+        outside: Vec<Vec2>,
+        success: usize,
+    ) -> Algo {
         let mut indices = Vec::with_capacity(grid.cells() * 2);
-        let choices = (0..grid.side()).map(|i| i as f32).collect::<Vec<_>>();
-        indices.extend(each_combination(&choices));
-        let a = 0.3;
+        let spacing = grid.cell();
+        indices.extend(
+            (0..grid.cells())
+                .map(|cell| {
+                    decode(cell, grid.side())
+                        .expect("Because we are decoding index within grid this should work.")
+                })
+                .filter(|&index| grid.is_empty_at(index) == Some(true))
+                .filter(|&index| {
+                    let (min, max) = cell_bounds(index, spacing);
+                    poisson.overlaps(min, max)
+                }),
+        );
+        // Indices of the deepest cells have to fit into `i32`.
+        let side = grid.side()[0].max(grid.side()[1]) as u32;
+        let bits = 32 - side.leading_zeros();
+        let a = self.throw_factor;
+        let max_level = self
+            .max_level
+            .min(f32::MANTISSA_DIGITS.min(31 - bits) as usize);
         Algo {
             a,
             grid,
             throws: (a * indices.len() as f64).ceil() as usize,
-            range: Uniform::new(0, indices.len()),
+            stats: EbeidaStats {
+                active: vec![indices.len()],
+                ..EbeidaStats::default()
+            },
             indices,
             level: 0,
-            success: 0,
-            outside: vec![],
-            mantissa_digits: f32::MANTISSA_DIGITS as usize,
+            success,
+            outside,
+            voids: vec![],
+            max_level,
+            exact: self.exact,
         }
     }
 }
 
+impl Creator for EbeidaConfig {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        self.fill(poisson, Grid::new(poisson), vec![], 0)
+    }
+}
+
 /// Implementation for the Ebeida algorithm
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Algo {
     grid: Grid,
-    indices: Vec<Vec2>,
+    indices: Vec<[i32; 2]>,
     level: usize,
-    range: Uniform<usize>,
     throws: usize,
     success: usize,
     outside: Vec<Vec2>,
-    mantissa_digits: usize,
+    voids: Vec<Void>,
+    max_level: usize,
+    exact: bool,
     a: f64,
+    stats: EbeidaStats,
+}
+
+/// Statistics of the Ebeida algorithm, which show where the time generating the sampling went.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EbeidaStats {
+    /// Amount of samples thrown into the cells and polygons.
+    pub throws: usize,
+    /// Amount of thrown samples which didn't end up in the sampling.
+    pub rejections: usize,
+    /// Amount of cells subdivided at each level.
+    pub subdivisions: Vec<usize>,
+    /// Amount of cells active at the start of each level.
+    pub active: Vec<usize>,
+}
+
+/// Convex polygon covering a part of the base level cell that no disk covers yet.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Void {
+    parent: [i32; 2],
+    polygon: Vec<Point>,
+    area: f64,
 }
 
 impl Algorithm for Algo {
@@ -53,73 +201,75 @@ impl Algorithm for Algo {
     where
         R: Rng,
     {
-        if self.indices.is_empty() {
+        if self.indices.is_empty() && self.voids.is_empty() {
             return None;
         }
-        while self.level < self.mantissa_digits {
+        let last = if self.exact {
+            self.max_level.min(VOID_LEVEL)
+        } else {
+            self.max_level
+        };
+        while self.level < last {
             while self.throws > 0 {
                 self.throws -= 1;
-                let index = rng.sample(self.range);
-                let cur = self.indices[index].clone();
-                let parent = get_parent(cur.clone(), self.level);
+                let index = rng.sample(Uniform::new(0, self.indices.len()));
+                let cur = self.indices[index];
+                let parent = get_parent(cur, self.level);
                 if !self
                     .grid
-                    .get(parent.clone())
+                    .is_empty_at(parent)
                     .expect("Indexing base grid by valid parent failed.")
-                    .is_empty()
                 {
+                    // Cells of occupied parents are dropped without throwing a sample into them.
                     self.indices.swap_remove(index);
                     if self.indices.is_empty() {
                         return None;
                     }
-                    self.range = Uniform::new(0, self.indices.len());
                 } else {
-                    let sample = choose_random_sample(rng, &self.grid, cur.clone(), self.level);
-                    if is_disk_free(
-                        &self.grid,
-                        poisson,
-                        cur.clone(),
-                        self.level,
-                        sample.clone(),
-                        &self.outside,
-                    ) {
+                    self.stats.throws += 1;
+                    let sample = choose_random_sample(rng, &self.grid, cur, self.level);
+                    if poisson.contains(sample) && self.is_free(poisson, parent, sample) {
                         self.grid
-                            .get_mut(parent)
-                            .expect("Indexing base grid by already indexed valid parent failed.")
-                            .push(sample.clone());
+                            .insert(parent, sample)
+                            .expect("Indexing base grid by already indexed valid parent failed.");
                         self.indices.swap_remove(index);
-                        if !self.indices.is_empty() {
-                            self.range = Uniform::new(0, self.indices.len());
-                        }
                         self.success += 1;
                         return Some(sample.into());
                     }
+                    self.stats.rejections += 1;
                 }
             }
-            self.subdivide(&poisson);
+            self.stats.subdivisions.push(self.indices.len());
+            self.subdivide(poisson);
+            self.stats.active.push(self.indices.len());
             if self.indices.is_empty() {
                 return None;
             }
-            self.range = Uniform::new(0, self.indices.len());
             self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
             self.level += 1;
         }
-        let index = rng.sample(self.range);
-        let cur = self.indices.swap_remove(index);
+        if self.exact {
+            return self.fill_voids(poisson, rng).map(Into::into);
+        }
+        // Cells left at the deepest level are tried at their corners until one of them fits.
         let side = 2usize.pow(self.level as u32);
-        let sample = index_to_sample(&cur, side);
-        if is_disk_free(
-            &self.grid,
-            poisson,
-            cur.clone(),
-            self.level,
-            sample.clone(),
-            &self.outside,
-        ) {
-            Some(sample.into())
-        } else {
-            None
+        while !self.indices.is_empty() {
+            let index = rng.sample(Uniform::new(0, self.indices.len()));
+            let cur = self.indices.swap_remove(index);
+            let sample = index_to_sample(cur, self.grid.cell() / (side as f32));
+            self.stats.throws += 1;
+            if poisson.contains(sample)
+                && is_disk_free(&self.grid, poisson, cur, self.level, sample, &self.outside)
+            {
+                self.grid
+                    .insert(get_parent(cur, self.level), sample)
+                    .expect("Indexing base grid by valid parent failed.");
+                self.success += 1;
+                return Some(sample.into());
+            }
+            self.stats.rejections += 1;
         }
+        None
     }
 
     fn size_hint(&self, poisson: &Builder) -> (usize, Option<usize>) {
@@ -127,67 +277,290 @@ impl Algorithm for Algo {
         // how much sphere can fill it at best case and just figure out how many fills are still needed.
         let side = 2usize.pow(self.level as u32);
         let spacing = self.grid.cell() / (side as f32);
-        let grid_volume = (self.indices.len() as f32) * spacing.powi(2);
-        let sphere_volume = sphere_volume(2.0 * poisson.radius, 2);
+        let voids = self.voids.iter().map(|v| v.area).sum::<f64>() as f32;
+        let grid_volume = (self.indices.len() as f32) * spacing.x() * spacing.y() + voids;
+        let sphere_volume = sphere_volume(2.0 * poisson.max_radius(), 2);
         let lower = grid_volume / sphere_volume;
-        let mut lower = lower.floor() as usize;
-        if lower > 0 {
-            lower -= 1;
-        }
+        let lower = (lower.floor() as usize).saturating_sub(1);
         // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
-        let upper = self.grid.cells() - self.success;
+        let upper = self.grid.cells().saturating_sub(self.success);
         (lower, Some(upper))
     }
 
     fn restrict(&mut self, sample: mint::Vector2<f32>) {
         let sample: Vec2 = sample.into();
         self.success += 1;
-        let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
+        let index = sample_to_index(&sample, self.grid.cell());
+        if let Err(sample) = self.grid.insert(index, sample) {
             self.outside.push(sample);
         }
     }
 
     fn stays_legal(&self, poisson: &Builder, sample: mint::Vector2<f32>) -> bool {
         let sample: Vec2 = sample.into();
-        let index = sample_to_index(&sample, self.grid.side());
-        is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
+        let index = sample_to_index(&sample, self.grid.cell());
+        is_disk_free(&self.grid, poisson, index, 0, sample, &self.outside)
     }
 }
 
 impl Algo {
+    /// Returns the statistics of the sampling generated thus far.
+    pub fn stats(&self) -> &EbeidaStats {
+        &self.stats
+    }
+
+    /// Returns the amount of times the cells are subdivided at most, which can be lower than the configured one on
+    /// large grids.
+    pub fn max_level(&self) -> usize {
+        self.max_level
+    }
+
+    /// Checks if the sample is far enough from the samples around the base level cell.
+    fn is_free(&self, poisson: &Builder, parent: [i32; 2], sample: Vec2) -> bool {
+        if !self.exact {
+            return is_disk_free(&self.grid, poisson, parent, 0, sample, &self.outside);
+        }
+        // Exact samples are kept a bit further away than needed, so they stay apart after moving them around in `f32`.
+        let radius = poisson.radius_at(sample.into()) + tolerance(poisson) as f32;
+        self.grid.neighbours(parent).chain(&self.outside).all(|&t| {
+            let distance = sqdist(t, sample, poisson.dimensions, poisson.poisson_type).sqrt();
+            distance >= radius + poisson.radius_at(t.into())
+        })
+    }
+
     fn subdivide(&mut self, poisson: &Builder) {
-        let choices = &[0.0, 1.0];
-        let (grid, outside, level) = (&self.grid, &self.outside, self.level);
+        let choices = &[0, 1];
+        let (grid, outside, level, exact) = (&self.grid, &self.outside, self.level, self.exact);
+        let spacing = grid.cell() / (2usize.pow(level as u32 + 1) as f32);
         self.indices.flat_map_inplace(|i| {
             each_combination(choices)
-                .map(move |n: Vec2| n + i.clone() * 2.0)
-                .filter(|c| !covered(grid, poisson, outside, c.clone(), level + 1))
+                .map(move |n| [2 * i[0] + n[0], 2 * i[1] + n[1]])
+                .filter(|&c| !covered(grid, poisson, outside, c, level + 1, exact))
+                .filter(|&c| {
+                    let (min, max) = cell_bounds(c, spacing);
+                    poisson.overlaps(min, max)
+                })
         });
     }
+
+    /// Clips the remaining cells into polygons and samples inside of them until they are all covered.
+    fn fill_voids<R>(&mut self, poisson: &Builder, rng: &mut R) -> Option<Vec2>
+    where
+        R: Rng,
+    {
+        let spacing = self.grid.cell() / 2f32.powi(self.level as i32);
+        for index in std::mem::take(&mut self.indices) {
+            let (min, max) = cell_bounds(index, spacing);
+            let polygon = void::rect(to_point(min), to_point(max));
+            let voids = self.carve(poisson, get_parent(index, self.level), polygon);
+            self.voids.extend(voids);
+        }
+        let largest = |voids: &[Void]| voids.iter().map(|v| v.area).fold(0.0, f64::max);
+        let mut bound = largest(&self.voids);
+        while !self.voids.is_empty() {
+            // Polygons only ever shrink, so they can be chosen in proportion to their area by rejecting them in
+            // proportion to how much smaller they are than the bound, which is tightened once rejections pile up.
+            let mut rejections = 0;
+            let index = loop {
+                let index = rng.sample(Uniform::new(0, self.voids.len()));
+                if rng.gen::<f64>() * bound < self.voids[index].area {
+                    break index;
+                }
+                rejections += 1;
+                if rejections > self.voids.len() {
+                    rejections = 0;
+                    bound = largest(&self.voids);
+                }
+            };
+            let point = void::random_point(rng, &self.voids[index].polygon);
+            self.stats.throws += 1;
+            let sample = Vec2::new(point[0] as f32, point[1] as f32);
+            let parent = self.voids[index].parent;
+            if poisson.contains(sample) && self.is_free(poisson, parent, sample) {
+                self.grid
+                    .insert(parent, sample)
+                    .expect("Indexing base grid by parent of a void failed.");
+                self.success += 1;
+                for i in (0..self.voids.len()).rev() {
+                    if self.grid.is_near(self.voids[i].parent, parent) {
+                        let Void {
+                            parent, polygon, ..
+                        } = self.voids.swap_remove(i);
+                        let pieces = cut(poisson, polygon, sample);
+                        self.voids.extend(
+                            pieces
+                                .into_iter()
+                                .filter_map(|p| to_void(poisson, parent, p)),
+                        );
+                    }
+                }
+                return Some(sample);
+            }
+            self.stats.rejections += 1;
+            // Polygons also cover thin slivers of the disks, so the ones missed are split to fit the gaps tighter.
+            let missed = self.voids.swap_remove(index);
+            for half in void::split(&missed.polygon).iter() {
+                let voids = self.carve(poisson, missed.parent, half.clone());
+                self.voids.extend(voids);
+            }
+        }
+        None
+    }
+
+    /// Removes the disks around the base level cell from the polygon.
+    fn carve(&self, poisson: &Builder, parent: [i32; 2], polygon: Vec<Point>) -> Vec<Void> {
+        let mut pieces = vec![polygon];
+        for &sample in self.grid.neighbours(parent).chain(&self.outside) {
+            pieces = pieces
+                .into_iter()
+                .flat_map(|p| cut(poisson, p, sample))
+                .collect();
+        }
+        pieces
+            .into_iter()
+            .filter_map(|p| to_void(poisson, parent, p))
+            .collect()
+    }
+}
+
+fn to_point(v: Vec2) -> Point {
+    [v.x() as f64, v.y() as f64]
+}
+
+/// Removes the disk of the sample and its periodic copies from the polygon.
+fn cut(poisson: &Builder, polygon: Vec<Point>, sample: Vec2) -> Vec<Vec<Point>> {
+    let images: &[i32] = match poisson.poisson_type {
+        Type::Periodic => &[-1, 0, 1],
+        Type::Normal => &[0],
+    };
+    let reach = 2.0 * poisson.max_radius() as f64;
+    let mut pieces = vec![polygon];
+    for [x, y] in each_combination(images) {
+        let center = to_point(sample + Vec2::new(x as f32, y as f32) * poisson.dimensions);
+        if pieces
+            .iter()
+            .all(|p| void::sqdist_to_bounds(p, center) >= reach * reach)
+        {
+            continue;
+        }
+        pieces = pieces
+            .into_iter()
+            .flat_map(|p| {
+                // Polygons get small fast enough that the smallest radius at the vertices approximates the smallest
+                // radius in the polygon.
+                let radius = p
+                    .iter()
+                    .map(|v| poisson.radius_at([v[0] as f32, v[1] as f32].into()))
+                    .fold(f32::MAX, f32::min);
+                let radius = (poisson.radius_at(sample.into()) + radius) as f64;
+                if void::sqdist_to_bounds(&p, center) < radius * radius {
+                    void::subtract(&p, center, radius)
+                } else {
+                    vec![p]
+                }
+            })
+            .collect();
+    }
+    pieces
+}
+
+/// Returns the void of the polygon, or `None` if it's too thin to fit a sample, outside of the sampled area or rejected
+/// by the predicate at every vertex.
+fn to_void(poisson: &Builder, parent: [i32; 2], polygon: Vec<Point>) -> Option<Void> {
+    let area = void::area(&polygon);
+    let (min, max) = void::bounds(&polygon);
+    let diameter = ((max[0] - min[0]).powi(2) + (max[1] - min[1]).powi(2)).sqrt();
+    let bounds = (
+        Vec2::new(min[0] as f32, min[1] as f32),
+        Vec2::new(max[0] as f32, max[1] as f32),
+    );
+    let rejected = polygon
+        .iter()
+        .all(|p| rejects_corner(poisson, Vec2::new(p[0] as f32, p[1] as f32)));
+    if area > tolerance(poisson) * diameter && poisson.overlaps(bounds.0, bounds.1) && !rejected {
+        Some(Void {
+            parent,
+            polygon,
+            area,
+        })
+    } else {
+        None
+    }
+}
+
+/// Gaps thinner than a few spacings of `f32` values can't be told apart from the disks around them, as moving the
+/// samples around the area rounds them by about that much.
+fn tolerance(poisson: &Builder) -> f64 {
+    4.0 * f32::EPSILON as f64 * poisson.dimensions.max_element() as f64
+}
+
+/// Checks if the predicate rejects the corner of a cell or polygon.
+/// Corners on the far edges are moved inside of the area first, as samples can't be at the edges themselves and
+/// otherwise the cells along them would keep being subdivided where the predicate rejects the area next to the edges.
+fn rejects_corner(poisson: &Builder, corner: Vec2) -> bool {
+    let mut inside = corner;
+    for n in 0..2 {
+        let side = poisson.dimensions[n];
+        inside[n] = match poisson.poisson_type {
+            Type::Normal => inside[n].min(f32::from_bits(side.to_bits() - 1)),
+            Type::Periodic => inside[n].rem_euclid(side),
+        };
+    }
+    poisson.rejects(inside)
 }
 
 fn covered(
     grid: &Grid,
     poisson: &Builder,
     outside: &[Vec2],
-    index: Vec2,
+    index: [i32; 2],
     level: usize,
+    exact: bool,
 ) -> bool {
     // TODO: This does 4^d checking of points even though it could be done 3^d
     let side = 2usize.pow(level as u32);
     let spacing = grid.cell() / (side as f32);
-    let sqradius = (2.0 * poisson.radius).powi(2);
-    let parent = get_parent(index.clone(), level);
-    each_combination(&[0.0, 1.0])
-        .map(|t| (index.clone() + t) * spacing)
+    let parent = get_parent(index, level);
+    let corners = each_combination(&[0, 1])
+        .map(|t| index_to_sample([index[0] + t[0], index[1] + t[1]], spacing))
+        .collect::<Vec<_>>();
+    // Cells get small fast enough that the smallest radius at the corners approximates the smallest radius in the cell.
+    let radius = corners
+        .iter()
+        .map(|&t| poisson.radius_at(t.into()))
+        .fold(f32::MAX, f32::min);
+    let covers = |&v: &Vec2, t: Vec2| {
+        let sqradius = (poisson.radius_at(v.into()) + radius).powi(2);
+        sqdist(v, t, poisson.dimensions, poisson.poisson_type) < sqradius
+    };
+    // Regions the predicate rejects can't be told apart from the corners of the cells, so cells it rejects at every
+    // corner count as covered instead of being subdivided forever.
+    if corners.iter().all(|&t| rejects_corner(poisson, t)) {
+        return true;
+    }
+    if exact {
+        // Only a disk covering all of the corners is sure to cover the whole cell.
+        return grid
+            .neighbours(parent)
+            .chain(outside)
+            .any(|v| corners.iter().all(|&t| covers(v, t)));
+    }
+    // Corners outside of the polygon or rejected by the predicate can't be sampled, so they count as covered.
+    // Parts of the polygon can still reach into the cell between them, so its vertices inside of the cell and the
+    // points where its edges cross the cell have to be covered too.
+    let (min, max) = cell_bounds(index, spacing);
+    let crossings = poisson
+        .polygon
+        .as_ref()
+        .map_or_else(Vec::new, |p| p.crossings(min, max))
+        .into_iter()
+        .filter(|&t| (0..2).all(|n| 0.0 <= t[n] && t[n] <= poisson.dimensions[n]));
+    corners
+        .into_iter()
+        .filter(|&t| poisson.accepts(t))
+        .chain(crossings)
         .all(|t| {
-            each_combination(&[-2.0, -1.0, 0.0, 1.0, 2.0])
-                .filter_map(|t| grid.get(parent.clone() + t))
-                .flat_map(|t| t)
-                .any(|v| sqdist(v.clone(), t.clone(), poisson.poisson_type) < sqradius)
-                || !is_valid(poisson, &outside, t)
+            rejects_corner(poisson, t)
+                || grid.neighbours(parent).chain(outside).any(|v| covers(v, t))
         })
 }
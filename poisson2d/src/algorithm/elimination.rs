@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::algorithm::{Algorithm, Creator};
+use crate::utils::math::{calc_max_radius, calc_samples};
+use crate::utils::*;
+use crate::Builder;
+
+/// Generates uniform non-maximal Poisson disk samplings with exactly the requested amount of samples with
+/// O(n log n) time and O(n) space complexity relative to the number of samples generated.
+/// The amount of samples is the one the builder was given or, if it was given a radius, the amount a sampling with
+/// that radius and relative radius 1 would have.
+/// Samples don't necessarily stay the radius of the builder apart and radius fields are ignored.
+/// As samples aren't kept any fixed distance apart, every sample stays legal.
+/// If a polygon or predicate leaves too little of the area for more candidates than samples to be found, every
+/// candidate found is kept and the sampling has fewer samples than requested.
+/// Based on Yuksel, Cem. "Sample elimination for generating Poisson disk sample sets." Computer Graphics Forum 34.2 (2015): 25-32.
+#[derive(Debug, Clone, Copy)]
+pub struct SampleElimination;
+
+impl Creator for SampleElimination {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        Algo {
+            grid: Grid::new(poisson),
+            restricted: vec![],
+            remaining: None,
+        }
+    }
+}
+
+/// Implementation for the sample elimination algorithm
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Algo {
+    grid: Grid,
+    restricted: Vec<Vec2>,
+    remaining: Option<Vec<Vec2>>,
+}
+
+impl Algorithm for Algo {
+    fn next<R>(&mut self, poisson: &mut Builder, rng: &mut R) -> Option<mint::Vector2<f32>>
+    where
+        R: Rng,
+    {
+        if self.remaining.is_none() {
+            self.remaining = Some(eliminate(poisson, rng, &self.grid, &self.restricted));
+        }
+        self.remaining.as_mut()?.pop().map(Into::into)
+    }
+
+    fn size_hint(&self, poisson: &Builder) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(ref remaining) => (remaining.len(), Some(remaining.len())),
+            None => {
+                let samples = target(poisson);
+                // Polygon or predicate might leave too little room for the candidates to be found.
+                let lower = if poisson.is_masked() { 0 } else { samples };
+                (lower, Some(samples))
+            }
+        }
+    }
+
+    fn restrict(&mut self, sample: mint::Vector2<f32>) {
+        self.restricted.push(sample.into());
+    }
+
+    fn stays_legal(&self, _: &Builder, _: mint::Vector2<f32>) -> bool {
+        // Samples aren't kept any fixed distance apart, so no sample can break the sampling.
+        true
+    }
+}
+
+/// Amount of candidates drawn for each sample.
+const CANDIDATES: usize = 5;
+/// Exponent of the weight function.
+const ALPHA: f32 = 8.0;
+/// Parameters of the weight limiting.
+const BETA: f32 = 0.65;
+const GAMMA: f32 = 1.5;
+
+/// Amount of samples the algorithm generates for the builder.
+fn target(poisson: &Builder) -> usize {
+    poisson
+        .samples
+        .unwrap_or_else(|| calc_samples(poisson.radius, poisson.dimensions))
+}
+
+/// Candidate ordered by its weight.
+struct Weighted(f32, usize);
+
+impl PartialEq for Weighted {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Weighted {}
+
+impl PartialOrd for Weighted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Weighted {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Draws random candidates from the sampled area and eliminates the ones with the most neighbours close to them
+/// until the target amount of samples remains.
+/// Restricted samples weigh the candidates around them but are never eliminated.
+fn eliminate<R>(poisson: &Builder, rng: &mut R, grid: &Grid, restricted: &[Vec2]) -> Vec<Vec2>
+where
+    R: Rng,
+{
+    let samples = target(poisson);
+    let spacing = grid.cell();
+    let cells = (0..grid.cells())
+        .map(|cell| {
+            decode(cell, grid.side())
+                .expect("Because we are decoding index within grid this should work.")
+        })
+        .filter(|&index| {
+            let (min, max) = cell_bounds(index, spacing);
+            poisson.overlaps(min, max)
+        })
+        .collect::<Vec<_>>();
+    if cells.is_empty() {
+        return vec![];
+    }
+
+    // Throwing candidates into random cells also measures how much of the cells the polygon and predicate cover.
+    let wanted = CANDIDATES * samples;
+    let mut points = Vec::with_capacity(wanted + restricted.len());
+    let mut throws = 0;
+    while points.len() < wanted && throws < 64 * wanted {
+        throws += 1;
+        let index = cells[rng.sample(Uniform::new(0, cells.len()))];
+        let sample = choose_random_sample(rng, grid, index, 0);
+        if poisson.contains(sample) {
+            points.push(sample);
+        }
+    }
+    let candidates = points.len();
+    if candidates <= samples {
+        return points;
+    }
+    points.extend_from_slice(restricted);
+
+    let area =
+        (cells.len() as f32) * spacing.x() * spacing.y() * (candidates as f32) / (throws as f32);
+    let max_radius = calc_max_radius(samples, area);
+    // Candidates closer than the limit all weigh the same, which keeps the samples from clustering.
+    let min_radius = max_radius * (1.0 - (samples as f32 / candidates as f32).powf(GAMMA)) * BETA;
+    let weight = |sqdist: f32| {
+        let dist = sqdist.sqrt().max(2.0 * min_radius);
+        (1.0 - dist / (2.0 * max_radius)).max(0.0).powf(ALPHA)
+    };
+
+    let mut buckets: Grid<usize> = Grid::new(&Builder {
+        radius: max_radius,
+        radius_field: None,
+        ..poisson.clone()
+    });
+    let side = buckets.side();
+    let indices = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let [x, y] = sample_to_index(p, buckets.cell());
+            if i < candidates {
+                // Candidates right at the far edges can round into the cells past them.
+                [x.min(side[0] as i32 - 1), y.min(side[1] as i32 - 1)]
+            } else {
+                [x, y]
+            }
+        })
+        .collect::<Vec<_>>();
+    // Restricted samples outside of the grid are checked against every candidate.
+    let mut outside = vec![];
+    for (i, &index) in indices.iter().enumerate() {
+        if let Err(i) = buckets.insert(index, i) {
+            outside.push(i);
+        }
+    }
+    let neighbours = (0..points.len())
+        .map(|i| {
+            // Small periodic grids can visit the same cell more than once.
+            let mut near = buckets
+                .neighbours(indices[i])
+                .chain(&outside)
+                .cloned()
+                .filter(|&j| j != i)
+                .collect::<Vec<_>>();
+            near.sort_unstable();
+            near.dedup();
+            near.into_iter()
+                .map(|j| {
+                    let sqdist = sqdist(
+                        points[i],
+                        points[j],
+                        poisson.dimensions,
+                        poisson.poisson_type,
+                    );
+                    (j, weight(sqdist))
+                })
+                .filter(|&(_, w)| w > 0.0)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut weights = neighbours
+        .iter()
+        .map(|near| near.iter().map(|&(_, w)| w).sum::<f32>())
+        .collect::<Vec<_>>();
+    let mut heap = (0..candidates)
+        .map(|i| Weighted(weights[i], i))
+        .collect::<BinaryHeap<_>>();
+    let mut removed = vec![false; candidates];
+    let mut alive = candidates;
+    while alive > samples {
+        let Weighted(w, i) = heap
+            .pop()
+            .expect("Heap should contain every alive candidate.");
+        // Entries are never updated in place, so outdated ones are skipped.
+        if removed[i] || w != weights[i] {
+            continue;
+        }
+        removed[i] = true;
+        alive -= 1;
+        for &(j, w) in &neighbours[i] {
+            if j < candidates && !removed[j] {
+                weights[j] -= w;
+                heap.push(Weighted(weights[j], j));
+            }
+        }
+    }
+    points
+        .into_iter()
+        .take(candidates)
+        .zip(removed)
+        .filter(|&(_, r)| !r)
+        .map(|(p, _)| p)
+        .collect()
+}
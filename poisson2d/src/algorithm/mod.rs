@@ -4,12 +4,16 @@ use std::fmt::Debug;
 
 use rand::Rng;
 
+pub use self::any::AnyAlgorithm;
+pub use self::bridson::{Bridson, BridsonConfig, Candidates};
+pub use self::ebeida::{Ebeida, EbeidaConfig, EbeidaStats};
+pub use self::elimination::SampleElimination;
 use crate::Builder;
-pub use self::bridson::Bridson;
-pub use self::ebeida::Ebeida;
 
+mod any;
 mod bridson;
 mod ebeida;
+mod elimination;
 
 /// Constructs new instance of the algorithm.
 pub trait Creator: Copy + Debug {
@@ -17,7 +21,7 @@ pub trait Creator: Copy + Debug {
     type Algo: Algorithm;
 
     /// Creates new and empty algorithm instance.
-    fn create(_: &Builder) -> Self::Algo;
+    fn create(&self, _: &Builder) -> Self::Algo;
 }
 
 /// Trait that describes a Poisson disk sampling generating algorithm.
@@ -33,6 +37,12 @@ pub trait Algorithm {
     /// Restricts the algorithm with an arbitrary sample.
     fn restrict(&mut self, _: mint::Vector2<f32>);
 
+    /// Restricts the algorithm with an arbitrary sample which the sampling grows outward from, if the algorithm grows
+    /// it from the samples found earlier.
+    fn seed(&mut self, sample: mint::Vector2<f32>) {
+        self.restrict(sample);
+    }
+
     /// Checks if a sample is valid for the Poisson disk sampling generated thus far by the algorithm.
     fn stays_legal(&self, _: &Builder, _: mint::Vector2<f32>) -> bool;
 }
@@ -0,0 +1,41 @@
+//! Module that contains tools for measuring the quality of Poisson disk samplings.
+//!
+//! Measurements can be averaged over any amount of samplings, which all need to have the same dimensions and type.
+
+use glam::Vec2;
+
+pub use self::spectrum::{periodogram, Periodogram, RadialSpectrum};
+pub use self::statistics::{
+    nearest_neighbours, pair_statistics, NearestNeighbours, PairStatistics,
+};
+use crate::sampling::Sampling;
+use crate::Type;
+
+mod spectrum;
+mod statistics;
+
+/// Returns the common dimensions and type of the samplings.
+fn common<'a, I>(samplings: I) -> (Vec2, Type)
+where
+    I: IntoIterator<Item = &'a Sampling>,
+{
+    let mut samplings = samplings.into_iter();
+    let first = samplings
+        .next()
+        .expect("At least one sampling should be given.");
+    let dimensions = first.dimensions();
+    let poisson_type = first.poisson_type();
+    for s in samplings {
+        assert_eq!(
+            dimensions,
+            s.dimensions(),
+            "Samplings should have the same dimensions."
+        );
+        assert_eq!(
+            poisson_type,
+            s.poisson_type(),
+            "Samplings should have the same type."
+        );
+    }
+    (dimensions.into(), poisson_type)
+}
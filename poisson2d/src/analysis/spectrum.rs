@@ -0,0 +1,194 @@
+use std::f64::consts::PI;
+
+use glam::Vec2;
+
+use crate::analysis::common;
+use crate::sampling::Sampling;
+use crate::Type;
+
+/// Averaged periodogram of samplings, which is the power spectrum of their samples.
+///
+/// Frequencies are integer multiples of one over the width and height of the sampled area, going from `-size / 2` to
+/// `size - size / 2 - 1` along both axes.
+/// Power is scaled so that uniformly random samples have power 1 at every frequency except zero.
+#[derive(Clone, Debug)]
+pub struct Periodogram {
+    size: usize,
+    dimensions: Vec2,
+    power: Vec<f32>,
+}
+
+/// Computes the periodogram of the samplings averaged over them with the amount of frequencies along each axis.
+///
+/// For `Type::Normal` samples are weighted with a Hann window, which keeps the edges of the sampled area from showing
+/// up in the spectrum but blurs it by about two frequencies.
+pub fn periodogram<'a, I>(samplings: I, size: usize) -> Periodogram
+where
+    I: IntoIterator<Item = &'a Sampling>,
+{
+    assert!(size > 0);
+    let samplings = samplings.into_iter().collect::<Vec<_>>();
+    let (dimensions, poisson_type) = common(samplings.iter().cloned());
+    let mut power = vec![0.0; size * size];
+    let mut sums = vec![(0.0, 0.0); size * size];
+    for sampling in &samplings {
+        sums.iter_mut().for_each(|s| *s = (0.0, 0.0));
+        let mut norm = 0.0;
+        for &sample in sampling.iter() {
+            let position = Vec2::from(sample) / dimensions;
+            let (x, y) = (position.x() as f64, position.y() as f64);
+            let weight = match poisson_type {
+                Type::Periodic => 1.0,
+                Type::Normal => window(x) * window(y),
+            };
+            if weight == 0.0 {
+                continue;
+            }
+            norm += weight * weight;
+            // Each wave is the product of the waves along the axes.
+            let xs = waves(x, size);
+            for (row, (c, d)) in sums.chunks_mut(size).zip(waves(y, size)) {
+                let (c, d) = (c * weight, d * weight);
+                for (sum, &(a, b)) in row.iter_mut().zip(&xs) {
+                    sum.0 += a * c - b * d;
+                    sum.1 += a * d + b * c;
+                }
+            }
+        }
+        if norm > 0.0 {
+            for (p, &(re, im)) in power.iter_mut().zip(&sums) {
+                *p += (re * re + im * im) / norm;
+            }
+        }
+    }
+    let power = power
+        .into_iter()
+        .map(|p| (p / samplings.len() as f64) as f32)
+        .collect();
+    Periodogram {
+        size,
+        dimensions,
+        power,
+    }
+}
+
+/// Hann window over [0, 1].
+fn window(t: f64) -> f64 {
+    if (0.0..=1.0).contains(&t) {
+        (PI * t).sin().powi(2)
+    } else {
+        0.0
+    }
+}
+
+/// Returns exp(-2πikt) for each frequency k of the periodogram.
+fn waves(t: f64, size: usize) -> Vec<(f64, f64)> {
+    let half = (size / 2) as f64;
+    (0..size)
+        .map(|k| {
+            let angle = -2.0 * PI * (k as f64 - half) * t;
+            (angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+impl Periodogram {
+    /// Returns the amount of frequencies along each axis.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the width and height of the area of the samplings.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.dimensions.into()
+    }
+
+    /// Returns the power at the frequency with the integer multiples of one over the width and height specified.
+    pub fn power(&self, x: i32, y: i32) -> f32 {
+        let half = (self.size / 2) as i32;
+        let (i, j) = (x + half, y + half);
+        assert!(0 <= i && i < self.size as i32 && 0 <= j && j < self.size as i32);
+        self.power[j as usize * self.size + i as usize]
+    }
+
+    /// Returns the frequency with the integer multiples of one over the width and height specified.
+    pub fn frequency(&self, x: i32, y: i32) -> mint::Vector2<f32> {
+        (Vec2::new(x as f32, y as f32) / self.dimensions).into()
+    }
+
+    /// Returns the power at all frequencies row by row starting from the lowest ones.
+    pub fn values(&self) -> &[f32] {
+        &self.power
+    }
+
+    /// Averages the periodogram over rings around frequency zero.
+    ///
+    /// Rings are as wide as the larger distance between neighbouring frequencies and only the rings which fit in the
+    /// periodogram completely are included.
+    pub fn radial(&self) -> RadialSpectrum {
+        let width = 1.0 / self.dimensions.x().min(self.dimensions.y());
+        let half = (self.size / 2) as i32;
+        let reach = half as f32 / self.dimensions.x().max(self.dimensions.y());
+        let rings = (reach / width - 0.5).floor().max(0.0) as usize + 1;
+        let mut sums = vec![(0usize, 0.0f64, 0.0f64); rings];
+        for y in -half..self.size as i32 - half {
+            for x in -half..self.size as i32 - half {
+                let ring = (Vec2::from(self.frequency(x, y)).length() / width).round() as usize;
+                if let Some(sum) = sums.get_mut(ring) {
+                    let p = self.power(x, y) as f64;
+                    *sum = (sum.0 + 1, sum.1 + p, sum.2 + p * p);
+                }
+            }
+        }
+        let power = sums
+            .iter()
+            .map(|&(count, sum, _)| (sum / count as f64) as f32)
+            .collect();
+        let anisotropy = sums
+            .iter()
+            .map(|&(count, sum, squares)| {
+                let mean = sum / count as f64;
+                if count < 2 || mean == 0.0 {
+                    0.0
+                } else {
+                    ((squares / count as f64 - mean * mean).max(0.0) / (mean * mean)) as f32
+                }
+            })
+            .collect();
+        RadialSpectrum {
+            width,
+            power,
+            anisotropy,
+        }
+    }
+}
+
+/// Radially averaged power spectrum with the anisotropy of each ring of frequencies.
+#[derive(Clone, Debug)]
+pub struct RadialSpectrum {
+    width: f32,
+    power: Vec<f32>,
+    anisotropy: Vec<f32>,
+}
+
+impl RadialSpectrum {
+    /// Returns the frequency at the middle of each ring, starting from zero.
+    pub fn frequencies(&self) -> Vec<f32> {
+        (0..self.power.len())
+            .map(|i| i as f32 * self.width)
+            .collect()
+    }
+
+    /// Returns the average power over each ring.
+    pub fn power(&self) -> &[f32] {
+        &self.power
+    }
+
+    /// Returns the variance of the power over each ring divided by its squared average.
+    /// Averaging more samplings lowers the anisotropy, which is about one over the amount of samplings for uniformly
+    /// random samples.
+    /// The ring of frequency zero has anisotropy zero.
+    pub fn anisotropy(&self) -> &[f32] {
+        &self.anisotropy
+    }
+}
@@ -0,0 +1,189 @@
+use std::f32::consts::PI;
+
+use glam::Vec2;
+
+use crate::analysis::common;
+use crate::sampling::Sampling;
+use crate::utils::sqdist;
+use crate::Type;
+
+/// Statistics of the distances between pairs of samples measured in rings of equal width around each sample.
+///
+/// For `Type::Normal` pairs are weighted with the translation edge correction, so that the samples near the edges
+/// of the sampled area count as much as the others.
+#[derive(Clone, Debug)]
+pub struct PairStatistics {
+    width: f32,
+    ripley_k: Vec<f32>,
+}
+
+/// Computes the pair statistics of the samplings averaged over them up to the distance with the amount of rings.
+/// The distance should be at most half of the width and height of the sampled area.
+pub fn pair_statistics<'a, I>(samplings: I, max_distance: f32, rings: usize) -> PairStatistics
+where
+    I: IntoIterator<Item = &'a Sampling>,
+{
+    assert!(max_distance > 0.0 && rings > 0);
+    let samplings = samplings.into_iter().collect::<Vec<_>>();
+    let (dimensions, poisson_type) = common(samplings.iter().cloned());
+    assert!(2.0 * max_distance <= dimensions.x().min(dimensions.y()));
+    let width = max_distance / rings as f32;
+    let area = (dimensions.x() * dimensions.y()) as f64;
+    let mut ripley_k = vec![0.0; rings];
+    let mut used = 0;
+    for sampling in samplings.iter().filter(|s| s.len() > 1) {
+        used += 1;
+        let mut counts = vec![0.0; rings];
+        for (i, &sample) in sampling.iter().enumerate() {
+            for j in sampling.within(sample, max_distance) {
+                if j == i {
+                    continue;
+                }
+                let (a, b): (Vec2, Vec2) = (sample.into(), sampling[j].into());
+                let distance = sqdist(a, b, dimensions, poisson_type).sqrt();
+                let ring = ((distance / width).ceil() as usize).clamp(1, rings) - 1;
+                // Share of the area where the pair could be seen if it was moved around.
+                let overlap = match poisson_type {
+                    Type::Periodic => area,
+                    Type::Normal => {
+                        let diff = (a - b).abs();
+                        ((dimensions.x() - diff.x()) * (dimensions.y() - diff.y())) as f64
+                    }
+                };
+                counts[ring] += area / overlap;
+            }
+        }
+        let scale = area / (sampling.len() * (sampling.len() - 1)) as f64;
+        let mut total = 0.0;
+        for (k, count) in ripley_k.iter_mut().zip(counts) {
+            total += count;
+            *k += total * scale;
+        }
+    }
+    let ripley_k = ripley_k
+        .into_iter()
+        .map(|k| (k / used.max(1) as f64) as f32)
+        .collect();
+    PairStatistics { width, ripley_k }
+}
+
+impl PairStatistics {
+    /// Returns the distance at the outer edge of each ring.
+    pub fn distances(&self) -> Vec<f32> {
+        (1..=self.ripley_k.len())
+            .map(|i| i as f32 * self.width)
+            .collect()
+    }
+
+    /// Returns the pair correlation function g(r) over each ring, which is the density of samples at the distance
+    /// from a sample relative to the density of all samples.
+    /// Uniformly random samples have pair correlation 1 at every distance.
+    pub fn pair_correlation(&self) -> Vec<f32> {
+        (0..self.ripley_k.len())
+            .map(|i| {
+                let (inner, outer) = (i as f32 * self.width, (i + 1) as f32 * self.width);
+                let previous = if i == 0 { 0.0 } else { self.ripley_k[i - 1] };
+                (self.ripley_k[i] - previous) / (PI * (outer * outer - inner * inner))
+            })
+            .collect()
+    }
+
+    /// Returns Ripley's K function at each distance, which is the expected amount of samples at most the distance
+    /// away from a sample divided by the density of all samples.
+    /// Uniformly random samples have K(r) = πr².
+    pub fn ripley_k(&self) -> &[f32] {
+        &self.ripley_k
+    }
+
+    /// Returns Ripley's L function at each distance, which is √(K(r) / π).
+    /// Uniformly random samples have L(r) = r.
+    pub fn ripley_l(&self) -> Vec<f32> {
+        self.ripley_k.iter().map(|k| (k / PI).sqrt()).collect()
+    }
+}
+
+/// Distances from each sample to its nearest neighbour.
+///
+/// For `Type::Normal` samples near the edges of the sampled area are missing the neighbours beyond the edges,
+/// which makes the distances slightly larger.
+#[derive(Clone, Debug)]
+pub struct NearestNeighbours {
+    distances: Vec<f32>,
+    density: f32,
+}
+
+/// Computes the nearest neighbour distances of the samples of all samplings.
+pub fn nearest_neighbours<'a, I>(samplings: I) -> NearestNeighbours
+where
+    I: IntoIterator<Item = &'a Sampling>,
+{
+    let samplings = samplings.into_iter().collect::<Vec<_>>();
+    let (dimensions, poisson_type) = common(samplings.iter().cloned());
+    let mut distances = vec![];
+    for sampling in &samplings {
+        for (i, &sample) in sampling.iter().enumerate() {
+            let nearest = sampling.nearest(sample, 2).into_iter().find(|&j| j != i);
+            if let Some(j) = nearest {
+                distances.push(
+                    sqdist(sample.into(), sampling[j].into(), dimensions, poisson_type).sqrt(),
+                );
+            }
+        }
+    }
+    let samples = samplings.iter().map(|s| s.len()).sum::<usize>();
+    let density = samples as f32 / (samplings.len() as f32 * dimensions.x() * dimensions.y());
+    NearestNeighbours { distances, density }
+}
+
+impl NearestNeighbours {
+    /// Returns the distance from each sample to its nearest neighbour.
+    /// Samples without neighbours are left out.
+    pub fn distances(&self) -> &[f32] {
+        &self.distances
+    }
+
+    /// Returns the smallest distance between two samples.
+    pub fn min(&self) -> f32 {
+        self.distances.iter().cloned().fold(f32::INFINITY, f32::min)
+    }
+
+    /// Returns the average distance or `None` if no sample has a neighbour.
+    pub fn mean(&self) -> Option<f32> {
+        if self.distances.is_empty() {
+            return None;
+        }
+        Some(self.distances.iter().sum::<f32>() / self.distances.len() as f32)
+    }
+
+    /// Returns the standard deviation of the distances or `None` if no sample has a neighbour.
+    pub fn standard_deviation(&self) -> Option<f32> {
+        let mean = self.mean()?;
+        let variance = self
+            .distances
+            .iter()
+            .map(|d| (d - mean).powi(2))
+            .sum::<f32>()
+            / self.distances.len() as f32;
+        Some(variance.sqrt())
+    }
+
+    /// Returns the amount of distances in each of the bins of equal width from zero to the distance specified.
+    /// Distances past the last bin are left out.
+    pub fn histogram(&self, max_distance: f32, bins: usize) -> Vec<usize> {
+        assert!(max_distance > 0.0 && bins > 0);
+        let mut histogram = vec![0; bins];
+        for &d in &self.distances {
+            if let Some(bin) = histogram.get_mut((d / max_distance * bins as f32) as usize) {
+                *bin += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Returns the Clark-Evans ratio, which is the average distance divided by the one expected from uniformly random
+    /// samples of the same density. Random samples have ratio 1 and evenly spread samples have larger ratios.
+    /// Returns `None` if no sample has a neighbour.
+    pub fn clark_evans(&self) -> Option<f32> {
+        self.mean().map(|mean| mean * 2.0 * self.density.sqrt())
+    }
+}
@@ -0,0 +1,151 @@
+//! Module that contains generation of infinite worlds split into chunks, which can be generated in any order.
+//!
+//! Chunk (x, y) covers [x · width, (x + 1) · width) × [y · height, (y + 1) · height), where the width and height
+//! are the ones of the builder.
+//! Chunks are split into four phases by the parity of their coordinates and each chunk is generated restricted by
+//! its neighbours of earlier phases. Chunks of the same phase never touch, so every chunk depends on the same few
+//! chunks around it no matter the order and the samples stay apart across the borders of the chunks.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use glam::Vec2;
+use rand::{Rng, SeedableRng};
+
+use crate::algorithm::Creator;
+use crate::utils::{mix_seed, phase};
+use crate::{Builder, Type};
+
+/// Generates Poisson disk samplings of the chunks of an infinite world from a world seed.
+///
+/// The samples of each chunk are cached, so the chunks it depends on are generated only once.
+#[derive(Clone, Debug)]
+pub struct ChunkGenerator<R, A>
+where
+    R: Rng + SeedableRng,
+    A: Creator,
+{
+    poisson: Builder,
+    algo: A,
+    seed: u64,
+    chunks: HashMap<[i32; 2], Vec<Vec2>>,
+    _rng: PhantomData<R>,
+}
+
+impl<R, A> ChunkGenerator<R, A>
+where
+    R: Rng + SeedableRng,
+    A: Creator,
+{
+    pub(crate) fn new(poisson: Builder, seed: u64, algo: A) -> Self {
+        assert_eq!(
+            Type::Normal,
+            poisson.poisson_type,
+            "Chunks can't be periodic."
+        );
+        assert!(poisson.polygon.is_none(), "Polygons aren't supported.");
+        let diameter = 2.0 * poisson.max_radius();
+        assert!(
+            diameter <= poisson.dimensions.min_element(),
+            "Chunks should be at least as wide and high as the largest disk."
+        );
+        ChunkGenerator {
+            poisson,
+            algo,
+            seed,
+            chunks: HashMap::new(),
+            _rng: PhantomData,
+        }
+    }
+
+    /// Returns the world seed of the generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the width and height of the chunks.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.poisson.dimensions.into()
+    }
+
+    /// Returns the radius of the generator.
+    pub fn radius(&self) -> f32 {
+        self.poisson.radius
+    }
+
+    /// Returns the samples of the chunk in world coordinates.
+    pub fn chunk(&mut self, x: i32, y: i32) -> Vec<mint::Vector2<f32>> {
+        let offset = Vec2::new(x as f32, y as f32) * self.poisson.dimensions;
+        self.generate([x, y])
+            .iter()
+            .map(|&sample| (sample + offset).into())
+            .collect()
+    }
+
+    /// Removes the chunk from the cache.
+    /// Generating it again gives the same samples.
+    pub fn unload(&mut self, x: i32, y: i32) {
+        self.chunks.remove(&[x, y]);
+    }
+
+    /// Removes every chunk from the cache.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    /// Returns the samples of the chunk relative to its corner, generating them first if needed.
+    fn generate(&mut self, chunk: [i32; 2]) -> &[Vec2] {
+        if !self.chunks.contains_key(&chunk) {
+            let samples = self.sample(chunk);
+            self.chunks.insert(chunk, samples);
+        }
+        &self.chunks[&chunk]
+    }
+
+    fn sample(&mut self, chunk: [i32; 2]) -> Vec<Vec2> {
+        let dimensions = self.poisson.dimensions;
+        let margin = Vec2::splat(2.0 * self.poisson.max_radius());
+        let offset = Vec2::new(chunk[0] as f32, chunk[1] as f32) * dimensions;
+        // Samples of the neighbours relative to this chunk and in world coordinates.
+        let mut restricted = vec![];
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let neighbour = [chunk[0] + dx, chunk[1] + dy];
+                if phase(neighbour) >= phase(chunk) {
+                    continue;
+                }
+                let shift = Vec2::new(dx as f32, dy as f32) * dimensions;
+                let origin = Vec2::new(neighbour[0] as f32, neighbour[1] as f32) * dimensions;
+                restricted.extend(
+                    self.generate(neighbour)
+                        .iter()
+                        .map(|&sample| (sample + shift, sample + origin))
+                        .filter(|&(sample, _)| {
+                            (-margin).cmplt(sample).all() && sample.cmplt(dimensions + margin).all()
+                        }),
+                );
+            }
+        }
+
+        let poisson = self.poisson.part(offset, dimensions);
+        let rng = R::seed_from_u64(mix_seed(self.seed, chunk));
+        let mut iter = poisson.build(rng, self.algo).into_iter();
+        for &(sample, _) in &restricted {
+            iter.restrict(sample.into());
+        }
+        // Moving the samples to world coordinates rounds them, which can push the ones right at the edges a tiny bit
+        // too close to the samples of the neighbours.
+        let world = &self.poisson;
+        iter.map(Vec2::from)
+            .filter(|&sample| {
+                let inner = margin.cmple(sample).all() && sample.cmplt(dimensions - margin).all();
+                inner
+                    || restricted.iter().all(|&(_, other)| {
+                        let sample = sample + offset;
+                        let radius = world.radius_at(sample.into()) + world.radius_at(other.into());
+                        (sample - other).length() >= radius
+                    })
+            })
+            .collect()
+    }
+}
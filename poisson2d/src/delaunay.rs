@@ -0,0 +1,413 @@
+//! Module that contains Delaunay triangulations and Voronoi diagrams of generated samplings.
+
+use glam::Vec2;
+
+use crate::sampling::Sampling;
+use crate::Type;
+
+/// Vertex of a triangulation which refers to a sample of the sampling.
+///
+/// For `Type::Periodic` the vertex can be a copy of the sample moved by whole multiples of the width and height of the
+/// sampled area, which is recorded in the shift. For `Type::Normal` the shift is always zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vertex {
+    /// Index of the sample in the sampling.
+    pub index: usize,
+    /// How many widths and heights the sample is moved by.
+    pub shift: [i32; 2],
+}
+
+/// Delaunay triangulation of a sampling.
+///
+/// For `Type::Normal` the triangles cover the convex hull of the samples.
+/// For `Type::Periodic` the triangulation is periodic on the torus, so each triangle appears once with its
+/// circumcenter inside the sampled area and its vertices are shifted to lie next to each other across the edges.
+/// This needs the samples to be dense enough for every empty circle to be smaller than the sampled area, which holds
+/// for any Poisson disk sampling with more than a handful of samples.
+///
+/// Triangles are counter-clockwise and Voronoi cells are counter-clockwise polygons around their samples.
+#[derive(Clone, Debug)]
+pub struct Triangulation {
+    samples: Vec<Vec2>,
+    dimensions: Vec2,
+    poisson_type: Type,
+    triangles: Vec<[Vertex; 3]>,
+    neighbours: Vec<Vec<Vertex>>,
+}
+
+impl Triangulation {
+    /// Triangulates the sampling.
+    pub fn new(sampling: &Sampling) -> Self {
+        let samples = sampling.iter().map(|&s| Vec2::from(s)).collect::<Vec<_>>();
+        let dimensions: Vec2 = sampling.dimensions().into();
+        let poisson_type = sampling.poisson_type();
+        let mut neighbours = vec![vec![]; samples.len()];
+        let triangles = match poisson_type {
+            Type::Normal => {
+                let points = samples.iter().map(|&s| to_point(s)).collect::<Vec<_>>();
+                let vertex = |index| Vertex {
+                    index,
+                    shift: [0, 0],
+                };
+                let mut triangles = vec![];
+                for t in triangulate(&points) {
+                    // Triangles using the corners of the enclosing triangle still connect the samples on the hull.
+                    for (k, &i) in t.iter().enumerate() {
+                        if i < samples.len() {
+                            for &j in &[t[(k + 1) % 3], t[(k + 2) % 3]] {
+                                if j < samples.len() {
+                                    neighbours[i].push(vertex(j));
+                                }
+                            }
+                        }
+                    }
+                    if t.iter().all(|&i| i < samples.len()) {
+                        triangles.push([vertex(t[0]), vertex(t[1]), vertex(t[2])]);
+                    }
+                }
+                triangles
+            }
+            Type::Periodic => {
+                let triangles = triangulate_periodic(&samples, dimensions);
+                for t in &triangles {
+                    for (k, v) in t.iter().enumerate() {
+                        for u in &[t[(k + 1) % 3], t[(k + 2) % 3]] {
+                            neighbours[v.index].push(Vertex {
+                                index: u.index,
+                                shift: [u.shift[0] - v.shift[0], u.shift[1] - v.shift[1]],
+                            });
+                        }
+                    }
+                }
+                triangles
+            }
+        };
+        for near in &mut neighbours {
+            near.sort_unstable();
+            near.dedup();
+        }
+        Triangulation {
+            samples,
+            dimensions,
+            poisson_type,
+            triangles,
+            neighbours,
+        }
+    }
+
+    /// Returns the width and height of the area of the sampling.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.dimensions.into()
+    }
+
+    /// Returns the type of the sampling.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Returns the triangles of the triangulation.
+    pub fn triangles(&self) -> &[[Vertex; 3]] {
+        &self.triangles
+    }
+
+    /// Returns the position of the vertex.
+    pub fn position(&self, vertex: Vertex) -> mint::Vector2<f32> {
+        let shift = Vec2::new(vertex.shift[0] as f32, vertex.shift[1] as f32);
+        (self.samples[vertex.index] + shift * self.dimensions).into()
+    }
+
+    /// Returns the samples which share an edge with the sample, shifted to lie next to it.
+    pub fn neighbours(&self, index: usize) -> &[Vertex] {
+        &self.neighbours[index]
+    }
+
+    /// Returns the Voronoi cell of the sample, which contains the points closer to it than to any other sample.
+    ///
+    /// For `Type::Normal` the cell is cut to the sampled area.
+    /// For `Type::Periodic` the cell is around the sample and can reach past the edges of the sampled area, so that
+    /// the cells tile the plane when repeated.
+    pub fn voronoi_cell(&self, index: usize) -> Vec<mint::Vector2<f32>> {
+        let sample = to_point(self.samples[index]);
+        let dims = to_point(self.dimensions);
+        let (min, max) = match self.poisson_type {
+            Type::Normal => ((0.0, 0.0), dims),
+            Type::Periodic => (
+                (sample.0 - dims.0, sample.1 - dims.1),
+                (sample.0 + dims.0, sample.1 + dims.1),
+            ),
+        };
+        let mut cell = vec![min, (max.0, min.1), max, (min.0, max.1)];
+        for &v in &self.neighbours[index] {
+            let other = to_point(self.position(v).into());
+            let normal = (other.0 - sample.0, other.1 - sample.1);
+            let middle = ((other.0 + sample.0) / 2.0, (other.1 + sample.1) / 2.0);
+            cell = clip(&cell, normal, normal.0 * middle.0 + normal.1 * middle.1);
+        }
+        cell.into_iter()
+            .map(|(x, y)| mint::Vector2 {
+                x: x as f32,
+                y: y as f32,
+            })
+            .collect()
+    }
+
+    /// Returns the Voronoi cells of all samples in the same order as the samples.
+    pub fn voronoi(&self) -> Vec<Vec<mint::Vector2<f32>>> {
+        (0..self.samples.len())
+            .map(|i| self.voronoi_cell(i))
+            .collect()
+    }
+}
+
+type Point = (f64, f64);
+
+fn to_point(v: Vec2) -> Point {
+    (v.x() as f64, v.y() as f64)
+}
+
+/// Positive if the points turn counter-clockwise.
+fn orient(a: Point, b: Point, c: Point) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Positive if d is inside the circumcircle of the counter-clockwise triangle abc.
+fn in_circle(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let (adx, ady) = (a.0 - d.0, a.1 - d.1);
+    let (bdx, bdy) = (b.0 - d.0, b.1 - d.1);
+    let (cdx, cdy) = (c.0 - d.0, c.1 - d.1);
+    (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+        + (bdx * bdx + bdy * bdy) * (cdx * ady - adx * cdy)
+        + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady)
+}
+
+fn circumcenter(a: Point, b: Point, c: Point) -> Point {
+    let (bx, by) = (b.0 - a.0, b.1 - a.1);
+    let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+    let d = 2.0 * (bx * cy - by * cx);
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    (a.0 + (cy * b2 - by * c2) / d, a.1 + (bx * c2 - cx * b2) / d)
+}
+
+/// Cuts away the part of the convex polygon where the dot product with the normal is larger than the offset.
+fn clip(polygon: &[Point], normal: Point, offset: f64) -> Vec<Point> {
+    let side = |p: Point| normal.0 * p.0 + normal.1 * p.1 - offset;
+    let mut result = vec![];
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (sa, sb) = (side(a), side(b));
+        if sa <= 0.0 {
+            result.push(a);
+        }
+        if (sa < 0.0 && sb > 0.0) || (sa > 0.0 && sb < 0.0) {
+            let t = sa / (sa - sb);
+            result.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+        }
+    }
+    result
+}
+
+/// Periodic triangulation built from the samples and the copies of them that lie near the sampled area.
+/// Only the triangles with their circumcenter inside the area are kept, which leaves exactly one copy of each.
+fn triangulate_periodic(samples: &[Vec2], dimensions: Vec2) -> Vec<[Vertex; 3]> {
+    let dims = to_point(dimensions);
+    if samples.is_empty() {
+        return vec![];
+    }
+    // Kept triangles are correct when their circumcircles fit in the margin, so it grows until they do.
+    let mut margin = 4.0 * (dims.0 * dims.1 / samples.len() as f64).sqrt();
+    loop {
+        let reach = (margin.min(dims.0), margin.min(dims.1));
+        let mut vertices = vec![];
+        let mut points = vec![];
+        for (index, &sample) in samples.iter().enumerate() {
+            let sample = to_point(sample);
+            let wraps = ((sample.0 / dims.0).floor(), (sample.1 / dims.1).floor());
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    let p = (
+                        sample.0 + (x as f64 - wraps.0) * dims.0,
+                        sample.1 + (y as f64 - wraps.1) * dims.1,
+                    );
+                    if -reach.0 <= p.0
+                        && p.0 < dims.0 + reach.0
+                        && -reach.1 <= p.1
+                        && p.1 < dims.1 + reach.1
+                    {
+                        vertices.push(Vertex {
+                            index,
+                            shift: [x - wraps.0 as i32, y - wraps.1 as i32],
+                        });
+                        points.push(p);
+                    }
+                }
+            }
+        }
+        let mut triangles = vec![];
+        let mut largest: f64 = 0.0;
+        for t in triangulate(&points) {
+            if t.iter().any(|&i| i >= points.len()) {
+                continue;
+            }
+            let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+            let center = circumcenter(a, b, c);
+            if 0.0 <= center.0 && center.0 < dims.0 && 0.0 <= center.1 && center.1 < dims.1 {
+                let radius = ((a.0 - center.0).powi(2) + (a.1 - center.1).powi(2)).sqrt();
+                largest = largest.max(radius);
+                triangles.push([vertices[t[0]], vertices[t[1]], vertices[t[2]]]);
+            }
+        }
+        if largest <= reach.0.min(reach.1) || margin >= dims.0.max(dims.1) {
+            return triangles;
+        }
+        margin *= 2.0;
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+/// Triangle of the incremental triangulation with the neighbouring triangle opposite to each vertex.
+struct Triangle {
+    vertices: [usize; 3],
+    neighbours: [usize; 3],
+}
+
+/// Delaunay triangulation of the points with Bowyer-Watson algorithm.
+/// The points are inserted into a large enclosing triangle, whose corners have indices right after the points and
+/// are left in the result.
+fn triangulate(points: &[Point]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n == 0 {
+        return vec![];
+    }
+    let (mut min, mut max) = (points[0], points[0]);
+    for p in points {
+        min = (min.0.min(p.0), min.1.min(p.1));
+        max = (max.0.max(p.0), max.1.max(p.1));
+    }
+    let size = (max.0 - min.0).max(max.1 - min.1).max(1.0);
+    let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+    let mut points = points.to_vec();
+    points.push((center.0 - 1000.0 * size, center.1 - 1000.0 * size));
+    points.push((center.0 + 1000.0 * size, center.1 - 1000.0 * size));
+    points.push((center.0, center.1 + 1000.0 * size));
+
+    // Inserting the points row by row keeps the walk to the next point short.
+    let rows = ((n as f64).sqrt() / 2.0).ceil();
+    let row = |p: Point| ((p.1 - min.1) / size * rows) as i64;
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| {
+        let (ri, rj) = (row(points[i]), row(points[j]));
+        let (xi, xj) = if ri.rem_euclid(2) == 0 {
+            (points[i].0, points[j].0)
+        } else {
+            (-points[i].0, -points[j].0)
+        };
+        ri.cmp(&rj).then(xi.total_cmp(&xj))
+    });
+
+    let mut triangles = vec![Triangle {
+        vertices: [n, n + 1, n + 2],
+        neighbours: [NONE; 3],
+    }];
+    let mut marks = vec![0];
+    let mut last = 0;
+    let mut cavity = vec![];
+    let mut stack = vec![];
+    let mut boundary = vec![];
+    for (mark, &i) in (1..).zip(&order) {
+        let p = points[i];
+        let found = locate(&triangles, &points, last, p);
+        if triangles[found].vertices.iter().any(|&v| points[v] == p) {
+            continue;
+        }
+
+        // Triangles whose circumcircles contain the point form a cavity around it.
+        cavity.clear();
+        marks[found] = mark;
+        stack.push(found);
+        while let Some(t) = stack.pop() {
+            cavity.push(t);
+            for &u in &triangles[t].neighbours {
+                if u != NONE && marks[u] != mark {
+                    let [a, b, c] = triangles[u].vertices;
+                    if in_circle(points[a], points[b], points[c], p) > 0.0 {
+                        marks[u] = mark;
+                        stack.push(u);
+                    }
+                }
+            }
+        }
+        boundary.clear();
+        for &t in &cavity {
+            let Triangle {
+                vertices,
+                neighbours,
+            } = &triangles[t];
+            for k in 0..3 {
+                let u = neighbours[k];
+                if u == NONE || marks[u] != mark {
+                    boundary.push((vertices[(k + 1) % 3], vertices[(k + 2) % 3], u));
+                }
+            }
+        }
+
+        // The cavity is replaced by triangles connecting its boundary to the point.
+        let mut created = Vec::with_capacity(boundary.len());
+        for (k, &(a, b, outer)) in boundary.iter().enumerate() {
+            let slot = match cavity.get(k) {
+                Some(&t) => t,
+                None => {
+                    triangles.push(Triangle {
+                        vertices: [0; 3],
+                        neighbours: [NONE; 3],
+                    });
+                    marks.push(0);
+                    triangles.len() - 1
+                }
+            };
+            triangles[slot] = Triangle {
+                vertices: [a, b, i],
+                neighbours: [NONE, NONE, outer],
+            };
+            marks[slot] = 0;
+            if outer != NONE {
+                let o = &mut triangles[outer];
+                let k = (0..3)
+                    .find(|&k| o.vertices[(k + 1) % 3] == b && o.vertices[(k + 2) % 3] == a)
+                    .expect("Triangle outside of the cavity should share the edge.");
+                o.neighbours[k] = slot;
+            }
+            created.push(slot);
+        }
+        for &t in &created {
+            let [a, b, _] = triangles[t].vertices;
+            let next = created.iter().find(|&&u| triangles[u].vertices[0] == b);
+            let previous = created.iter().find(|&&u| triangles[u].vertices[1] == a);
+            triangles[t].neighbours[0] = *next.expect("Cavity boundary should be closed.");
+            triangles[t].neighbours[1] = *previous.expect("Cavity boundary should be closed.");
+        }
+        last = created[0];
+    }
+    triangles.into_iter().map(|t| t.vertices).collect()
+}
+
+/// Walks from the triangle towards the point until reaching the triangle containing it.
+fn locate(triangles: &[Triangle], points: &[Point], start: usize, p: Point) -> usize {
+    let mut t = start;
+    // Rotating the first edge checked keeps the walk from circling around.
+    for step in 0.. {
+        let Triangle {
+            vertices,
+            neighbours,
+        } = &triangles[t];
+        let next = (0..3).map(|k| (k + step) % 3).find(|&k| {
+            let (a, b) = (points[vertices[(k + 1) % 3]], points[vertices[(k + 2) % 3]]);
+            neighbours[k] != NONE && orient(a, b, p) < 0.0
+        });
+        match next {
+            Some(k) => t = neighbours[k],
+            None => return t,
+        }
+    }
+    unreachable!()
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+/// Error for invalid values given to the builder or generator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// Radius isn't in (0, max].
+    InvalidRadius {
+        /// Radius given.
+        radius: f32,
+        /// Largest radius allowed.
+        max: f32,
+    },
+    /// Amount of samples is zero.
+    ZeroSamples,
+    /// Relative radius isn't in (0, 1].
+    InvalidRelativeRadius(f32),
+    /// Width or height of the sampled area isn't a finite value larger than zero.
+    InvalidDomain {
+        /// Width given.
+        width: f32,
+        /// Height given.
+        height: f32,
+    },
+    /// Sampled area is too small for the exact amount of samples requested.
+    TooManySamples {
+        /// Amount of samples requested.
+        requested: usize,
+        /// Largest amount of samples found to fit.
+        max: usize,
+    },
+    /// Builder has a radius field, which multi-class sampling doesn't support.
+    UnsupportedRadiusField,
+    /// Throw factor of Ebeida isn't finite and larger than zero.
+    InvalidThrowFactor(f64),
+    /// Maximum level of subdivision of Ebeida is zero.
+    ZeroMaxLevel,
+    /// Amount of candidates Bridson throws around an active sample is zero.
+    ZeroCandidates,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::InvalidRadius { radius, max } => {
+                write!(f, "radius {} should be in (0, {}]", radius, max)
+            }
+            Error::ZeroSamples => write!(f, "amount of samples should be larger than 0"),
+            Error::InvalidRelativeRadius(relative) => {
+                write!(f, "relative radius {} should be in (0, 1]", relative)
+            }
+            Error::InvalidDomain { width, height } => write!(
+                f,
+                "width {} and height {} should be finite and larger than 0",
+                width, height
+            ),
+            Error::TooManySamples { requested, max } => write!(
+                f,
+                "{} samples were requested, but only {} were found to fit",
+                requested, max
+            ),
+            Error::UnsupportedRadiusField => {
+                write!(f, "radius fields aren't supported by multi-class sampling")
+            }
+            Error::InvalidThrowFactor(throw_factor) => write!(
+                f,
+                "throw factor {} should be finite and larger than 0",
+                throw_factor
+            ),
+            Error::ZeroMaxLevel => write!(f, "maximum level should be at least 1"),
+            Error::ZeroCandidates => write!(f, "at least one candidate should be thrown"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
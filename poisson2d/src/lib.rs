@@ -1,6 +1,6 @@
 //! # Poisson disk sampling
 //!
-//! Generates a sampling of points in [0, 1)<sup>2</sup> where:
+//! Generates a sampling of points in [0, 1)<sup>2</sup>, or in any other axis-aligned rectangle, where:
 //!
 //! * Sample points fill the space uniformly.
 //! * Sample points stay a given minimum distance apart.
@@ -31,6 +31,42 @@
 //! }
 //! ````
 //!
+//! Generate a maximal Poisson disk sampling in [0, 1)<sup>2</sup> with disk radius 0.01, where no gaps are left
+//! for another disk to fit.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.01, Type::Normal)
+//!             .build(SmallRng::from_entropy(), algorithm::EbeidaConfig::new().with_exact(true));
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a Poisson disk sampling with the throws of the Ebeida algorithm tuned and see where the time went.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let config = algorithm::EbeidaConfig::new().with_throw_factor(0.5).with_max_level(16);
+//!     let mut iter = Builder::with_radius(0.01, Type::Normal)
+//!         .build(SmallRng::from_entropy(), config)
+//!         .into_iter();
+//!     let samples = iter.by_ref().collect::<Vec<_>>();
+//!     let stats = iter.algorithm().stats();
+//!     assert_eq!(samples.len(), stats.throws - stats.rejections);
+//!     println!("{:?}", stats);
+//! }
+//! ````
+//!
 //! Generate a tiling Poisson disk sampling in [0, 1)<sup>2</sup> with approximately 100 samples
 //! and relative disk radius 0.9 using a faster but less accurate algorithm.
 //!
@@ -48,68 +84,566 @@
 //!     }
 //! }
 //! ````
+//!
+//! Generate a tiling Poisson disk sampling in [0, 1)<sup>2</sup> with exactly 100 samples.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_samples(100, 0.9, Type::Periodic)
+//!             .build(SmallRng::from_entropy(), algorithm::SampleElimination);
+//!     let samples = poisson.generate();
+//!     assert_eq!(100, samples.len());
+//! }
+//! ````
+//!
+//! Generate a non-tiling Poisson disk sampling in [0, 1)<sup>2</sup> with exactly 100 samples for any algorithm
+//! by searching for the radius giving that many samples.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_samples(100, 0.8, Type::Normal)
+//!             .build(SmallRng::from_entropy(), algorithm::Bridson);
+//!     let (samples, radius) = poisson.generate_exact(100).unwrap();
+//!     assert_eq!(100, samples.len());
+//!     println!("{:?} with radius {}", samples, radius);
+//! }
+//! ````
+//!
+//! Generate a non-tiling Poisson disk sampling in [0, 4) × [0, 1) with approximately 400 samples
+//! and relative disk radius 0.8.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_domain_samples(4.0, 1.0, 400, 0.8, Type::Normal)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Find the samples near a point of a generated sampling.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.05, Type::Periodic)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let samples = poisson.generate();
+//!     for i in samples.within([0.0, 0.0].into(), 0.2) {
+//!         println!("{:?} is close to the corner", samples[i]);
+//!     }
+//!     let closest = samples.nearest([0.5, 0.5].into(), 1);
+//!     println!("{:?} is closest to the center", samples[closest[0]]);
+//! }
+//! ````
+//!
+//! Triangulate a tiling sampling and find the Voronoi cells around the samples, which tile across the edges too.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.05, Type::Periodic)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let triangulation = poisson.generate().triangulate();
+//!     for triangle in triangulation.triangles() {
+//!         println!("{:?}", triangle.iter().map(|&v| triangulation.position(v)).collect::<Vec<_>>());
+//!     }
+//!     println!("{:?}", triangulation.voronoi());
+//! }
+//! ````
+//!
+//! Generate a Poisson disk sampling with the algorithm chosen while the program runs, such as from a configuration
+//! file.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let maximal = std::env::args().any(|arg| arg == "--maximal");
+//!     let algo = if maximal {
+//!         algorithm::AnyAlgorithm::from(algorithm::EbeidaConfig::new().with_exact(true))
+//!     } else {
+//!         algorithm::AnyAlgorithm::from(algorithm::Bridson)
+//!     };
+//!     let poisson = Builder::with_radius(0.01, Type::Normal).build(SmallRng::from_entropy(), algo);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a sampling which gets sparser towards the right edge with disk radius going from 0.01 to 0.05.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.01, Type::Normal)
+//!             .with_radius_field(0.05, |p| 0.01 + 0.04 * p.x)
+//!             .build(SmallRng::from_entropy(), algorithm::Bridson);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a sampling which leaves out a lake in the middle.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.01, Type::Normal)
+//!             .with_predicate(|p| (p.x - 0.5).hypot(p.y - 0.5) > 0.2)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a sampling inside a triangle with a square hole in the middle.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//! use poisson2d::polygon::Polygon;
+//!
+//! fn main() {
+//!     let polygon = Polygon::new(vec![[0.0, 0.0].into(), [1.0, 0.0].into(), [0.5, 1.0].into()])
+//!         .with_hole(vec![[0.4, 0.2].into(), [0.6, 0.2].into(), [0.6, 0.4].into(), [0.4, 0.4].into()]);
+//!     let poisson =
+//!         Builder::with_radius(0.02, Type::Normal)
+//!             .with_polygon(polygon)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a sampling on the surface of the unit sphere where samples stay at least 0.1 radians apart along great
+//! circles.
+//!
+//! ````rust
+//! # use poisson2d::algorithm;
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//! use poisson2d::spherical;
+//!
+//! fn main() {
+//!     let poisson =
+//!         spherical::Builder::with_radius(0.05)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let samples = poisson.generate();
+//!     println!("{:?}", samples);
+//! }
+//! ````
+//!
+//! Generate a sampling with three classes of samples, such as trees, bushes and flowers, where each class
+//! and each group of sparser classes is a Poisson disk sampling on its own.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type};
+//! # use rand::SeedableRng;
+//! # use rand::rngs::SmallRng;
+//! use poisson2d::multiclass::Classes;
+//!
+//! fn main() {
+//!     let poisson =
+//!         Builder::with_radius(0.01, Type::Normal)
+//!             .build_with_classes(SmallRng::from_entropy(), Classes::from_radii(&[0.05, 0.02, 0.01]));
+//!     for (class, sample) in poisson {
+//!         println!("{}: {:?}", class, sample);
+//!     }
+//! }
+//! ````
+//!
+//! Generate the samples of an infinite world in chunks of size 16 × 16 around the player, where chunks come out the
+//! same for the same world seed no matter the order they are generated in.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type, algorithm};
+//! use rand::rngs::SmallRng;
+//!
+//! fn main() {
+//!     let mut world =
+//!         Builder::with_domain(16.0, 16.0, 1.0, Type::Normal)
+//!             .build_chunked::<SmallRng, _>(42, algorithm::Ebeida);
+//!     for y in -1..=1 {
+//!         for x in -1..=1 {
+//!             println!("({}, {}): {:?}", x, y, world.chunk(x, y));
+//!         }
+//!     }
+//! }
+//! ````
+//!
+//! Report values from user input that the builder can't use instead of panicking.
+//!
+//! ````rust
+//! # use poisson2d::{Builder, Type};
+//!
+//! fn main() {
+//!     match Builder::try_with_samples(0, 0.8, Type::Normal) {
+//!         Ok(builder) => println!("{:?}", builder),
+//!         Err(error) => println!("Can't generate: {}", error),
+//!     }
+//! }
+//! ````
+//!
+//! # Features
+//!
+//! With the `serde` feature `Builder`, `Type`, `PoissonIter` and their multi-class counterparts `Classes` and
+//! `ClassIter` can be serialized, so a half-finished generation can be saved and resumed later with identical results
+//! as long as its random number generator can be serialized too.
+//! Builders with a radius field can't be serialized, and neither can samplings on the sphere.
+//!
+//! With the `rayon` feature `Generator::generate_parallel` splits the area into tiles and generates them on all
+//! threads of the rayon thread pool.
 
-use std::marker::PhantomData;
+use std::fmt;
+use std::sync::Arc;
 
-use rand::Rng;
+use glam::Vec2;
+use rand::{Rng, SeedableRng};
 
 use crate::algorithm::{Algorithm, Creator};
+use crate::chunked::ChunkGenerator;
+pub use crate::error::Error;
+use crate::multiclass::{ClassGenerator, Classes};
+use crate::polygon::Polygon;
+use crate::sampling::Sampling;
 use crate::utils::math::calc_radius;
+use crate::utils::sqdist;
 
 pub mod algorithm;
+pub mod analysis;
+pub mod chunked;
+pub mod delaunay;
+mod error;
+pub mod multiclass;
+#[cfg(feature = "rayon")]
+mod parallel;
+pub mod polygon;
+pub mod sampling;
+pub mod spherical;
 mod utils;
+pub mod validity;
 
 /// Enum for determining the type of Poisson disk sampling.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Type {
     /// Acts like there is void all around the space placing no restrictions to sides.
+    #[default]
     Normal,
     /// Makes the space wrap around on edges allowing tiling of the generated Poisson disk sampling.
     Periodic,
 }
 
-impl Default for Type {
-    fn default() -> Type {
-        Type::Normal
+/// Function giving the disk radius at each point of the sampling.
+#[derive(Clone)]
+struct RadiusField {
+    function: Arc<dyn Fn(mint::Vector2<f32>) -> f32 + Send + Sync>,
+    max_radius: f32,
+}
+
+impl fmt::Debug for RadiusField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RadiusField")
+            .field("max_radius", &self.max_radius)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RadiusField {
+    fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Err(serde::ser::Error::custom(
+            "radius fields can't be serialized",
+        ))
+    }
+}
+
+impl PartialEq for RadiusField {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.function, &other.function) && self.max_radius == other.max_radius
+    }
+}
+
+/// Function telling which points of the sampled area can have samples.
+#[derive(Clone)]
+struct Predicate {
+    function: Arc<dyn Fn(mint::Vector2<f32>) -> bool + Send + Sync>,
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicate").finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Predicate {
+    fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Err(serde::ser::Error::custom("predicates can't be serialized"))
+    }
+}
+
+impl PartialEq for Predicate {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.function, &other.function)
+    }
+}
+
+/// Checks that the width and height are finite and larger than 0.
+fn check_domain(width: f32, height: f32) -> Result<Vec2, Error> {
+    if 0.0 < width && 0.0 < height && width.is_finite() && height.is_finite() {
+        Ok(Vec2::new(width, height))
+    } else {
+        Err(Error::InvalidDomain { width, height })
+    }
+}
+
+/// Checks that the radius is in (0, max].
+pub(crate) fn check_radius(radius: f32, max: f32) -> Result<(), Error> {
+    if 0.0 < radius && radius <= max {
+        Ok(())
+    } else {
+        Err(Error::InvalidRadius { radius, max })
     }
 }
 
 /// Builder for the generator.
-#[derive(Default, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Builder {
     radius: f32,
+    dimensions: Vec2,
     poisson_type: Type,
+    samples: Option<usize>,
+    // Functions can't be restored, so builders with a radius field or predicate fail to serialize.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_deserializing, skip_serializing_if = "Option::is_none")
+    )]
+    radius_field: Option<RadiusField>,
+    polygon: Option<Polygon>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_deserializing, skip_serializing_if = "Option::is_none")
+    )]
+    predicate: Option<Predicate>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            radius: 0.0,
+            dimensions: Vec2::one(),
+            poisson_type: Type::default(),
+            samples: None,
+            radius_field: None,
+            polygon: None,
+            predicate: None,
+        }
+    }
 }
 
 impl Builder {
     /// New Builder with type of distribution and radius specified.
     /// The radius should be in (0, √2 / 2]
     pub fn with_radius(radius: f32, poisson_type: Type) -> Self {
-        assert!(0.0 < radius && radius <= 2f32.sqrt() / 2.0);
-        Builder {
-            radius,
-            poisson_type,
-        }
+        Builder::try_with_radius(radius, poisson_type).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder with type of distribution and radius specified.
+    /// Fails if the radius isn't in (0, √2 / 2]
+    pub fn try_with_radius(radius: f32, poisson_type: Type) -> Result<Self, Error> {
+        Builder::try_with_domain(1.0, 1.0, radius, poisson_type)
     }
 
     /// New Builder with type of distribution and relative radius specified.
     /// The relative radius should be in (0, 1]
     pub fn with_relative_radius(relative: f32, poisson_type: Type) -> Self {
-        assert!(0.0 < relative && relative <= 1.0);
-        Builder {
+        Builder::try_with_relative_radius(relative, poisson_type)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder with type of distribution and relative radius specified.
+    /// Fails if the relative radius isn't in (0, 1]
+    pub fn try_with_relative_radius(relative: f32, poisson_type: Type) -> Result<Self, Error> {
+        if !(0.0 < relative && relative <= 1.0) {
+            return Err(Error::InvalidRelativeRadius(relative));
+        }
+        Ok(Builder {
             radius: relative * 2f32.sqrt() / 2.0,
+            dimensions: Vec2::one(),
             poisson_type,
-        }
+            ..Builder::default()
+        })
     }
 
     /// New Builder with type of distribution, approximate amount of samples and relative radius specified.
     /// The amount of samples should be larger than 0.
     /// The relative radius should be in (0, 1].
     pub fn with_samples(samples: usize, relative: f32, poisson_type: Type) -> Self {
-        Builder {
-            radius: calc_radius(samples, relative, poisson_type),
+        Builder::try_with_samples(samples, relative, poisson_type)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder with type of distribution, approximate amount of samples and relative radius specified.
+    /// Fails if the amount of samples is 0 or the relative radius isn't in (0, 1].
+    pub fn try_with_samples(
+        samples: usize,
+        relative: f32,
+        poisson_type: Type,
+    ) -> Result<Self, Error> {
+        Builder::try_with_domain_samples(1.0, 1.0, samples, relative, poisson_type)
+    }
+
+    /// New Builder generating in [0, width) × [0, height) with type of distribution and radius specified.
+    /// The width and height should be larger than 0.
+    /// The radius should be in (0, √(width² + height²) / 2]
+    pub fn with_domain(width: f32, height: f32, radius: f32, poisson_type: Type) -> Self {
+        Builder::try_with_domain(width, height, radius, poisson_type)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder generating in [0, width) × [0, height) with type of distribution and radius specified.
+    /// Fails if the width or height isn't larger than 0 or the radius isn't in (0, √(width² + height²) / 2]
+    pub fn try_with_domain(
+        width: f32,
+        height: f32,
+        radius: f32,
+        poisson_type: Type,
+    ) -> Result<Self, Error> {
+        let dimensions = check_domain(width, height)?;
+        check_radius(radius, dimensions.length() / 2.0)?;
+        Ok(Builder {
+            radius,
+            dimensions,
             poisson_type,
-        }
+            ..Builder::default()
+        })
+    }
+
+    /// New Builder generating in [0, width) × [0, height) with type of distribution, approximate amount of samples
+    /// and relative radius specified.
+    /// The width and height should be larger than 0.
+    /// The amount of samples should be larger than 0.
+    /// The relative radius should be in (0, 1].
+    pub fn with_domain_samples(
+        width: f32,
+        height: f32,
+        samples: usize,
+        relative: f32,
+        poisson_type: Type,
+    ) -> Self {
+        Builder::try_with_domain_samples(width, height, samples, relative, poisson_type)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder generating in [0, width) × [0, height) with type of distribution, approximate amount of samples
+    /// and relative radius specified.
+    /// Fails if the width or height isn't larger than 0, the amount of samples is 0 or the relative radius isn't in
+    /// (0, 1].
+    pub fn try_with_domain_samples(
+        width: f32,
+        height: f32,
+        samples: usize,
+        relative: f32,
+        poisson_type: Type,
+    ) -> Result<Self, Error> {
+        let dimensions = check_domain(width, height)?;
+        Ok(Builder {
+            radius: calc_radius(samples, relative, dimensions, poisson_type)?,
+            dimensions,
+            poisson_type,
+            samples: Some(samples),
+            ..Builder::default()
+        })
+    }
+
+    /// Makes the radius vary over the sampled area as given by the radius field.
+    /// The radius of the builder becomes the smallest allowed radius and values of the field are clamped to
+    /// [radius, max_radius].
+    /// Two samples stay at least the sum of their radii apart.
+    /// The maximum radius should be larger than or equal to the radius.
+    pub fn with_radius_field<F>(self, max_radius: f32, field: F) -> Self
+    where
+        F: Fn(mint::Vector2<f32>) -> f32 + Send + Sync + 'static,
+    {
+        self.try_with_radius_field(max_radius, field)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Makes the radius vary over the sampled area as given by the radius field.
+    /// Fails if the radius of the builder is larger than the maximum radius.
+    pub fn try_with_radius_field<F>(mut self, max_radius: f32, field: F) -> Result<Self, Error>
+    where
+        F: Fn(mint::Vector2<f32>) -> f32 + Send + Sync + 'static,
+    {
+        check_radius(self.radius, max_radius)?;
+        self.radius_field = Some(RadiusField {
+            function: Arc::new(field),
+            max_radius,
+        });
+        Ok(self)
+    }
+
+    /// Restricts the samples to the inside of the polygon.
+    /// Parts of the polygon outside of the sampled area are ignored.
+    pub fn with_polygon(mut self, polygon: Polygon) -> Self {
+        self.polygon = Some(polygon);
+        self
+    }
+
+    /// Only keeps samples at the points the predicate accepts, such as to leave out water or steep slopes.
+    /// The predicate is called for every candidate sample, so it should be cheap.
+    /// Regions the predicate accepts which are much smaller than the radius can be left empty.
+    pub fn with_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(mint::Vector2<f32>) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Predicate {
+            function: Arc::new(predicate),
+        });
+        self
     }
 
     /// Returns the radius of the generator.
@@ -117,22 +651,151 @@ impl Builder {
         self.radius
     }
 
+    /// Returns the amount of samples the builder was given, if it was given one.
+    pub fn samples(&self) -> Option<usize> {
+        self.samples
+    }
+
+    /// Returns the largest radius any sample of the generator can have.
+    pub fn max_radius(&self) -> f32 {
+        self.radius_field
+            .as_ref()
+            .map_or(self.radius, |field| field.max_radius)
+    }
+
+    /// Returns the radius of a sample at the given point.
+    pub fn radius_at(&self, point: mint::Vector2<f32>) -> f32 {
+        match self.radius_field {
+            Some(ref field) => (field.function)(point)
+                .max(self.radius)
+                .min(field.max_radius),
+            None => self.radius,
+        }
+    }
+
+    /// Returns the width and height of the area the generator samples.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.dimensions.into()
+    }
+
     /// Returns the type of the generator.
     pub fn poisson_type(&self) -> Type {
         self.poisson_type
     }
 
+    /// Returns the polygon the samples are restricted to.
+    pub fn polygon(&self) -> Option<&Polygon> {
+        self.polygon.as_ref()
+    }
+
+    /// Checks if the sample is in the sampled area.
+    fn contains(&self, sample: Vec2) -> bool {
+        (0..2).all(|n| 0.0 <= sample[n] && sample[n] < self.dimensions[n]) && self.accepts(sample)
+    }
+
+    /// Checks if the point is inside of the polygon and accepted by the predicate.
+    fn accepts(&self, point: Vec2) -> bool {
+        self.polygon.iter().all(|p| p.contains_point(point)) && !self.rejects(point)
+    }
+
+    /// Checks if the predicate rejects the point.
+    fn rejects(&self, point: Vec2) -> bool {
+        self.predicate
+            .as_ref()
+            .is_some_and(|p| !(p.function)(point.into()))
+    }
+
+    /// Checks if a polygon or predicate leaves parts of the area out, whose size isn't known.
+    fn is_masked(&self) -> bool {
+        self.polygon.is_some() || self.predicate.is_some()
+    }
+
+    /// Checks if the axis-aligned rectangle has any area in common with the polygon.
+    fn overlaps(&self, min: Vec2, max: Vec2) -> bool {
+        self.polygon.iter().all(|p| p.overlaps(min, max))
+    }
+
     /// Builds generator with random number generator and algorithm specified.
-    pub fn build<R, A>(self, rng: R, _algo: A) -> Generator<R, A>
+    pub fn build<R, A>(self, rng: R, algo: A) -> Generator<R, A>
     where
         R: Rng,
         A: Creator,
     {
-        Generator::new(self, rng)
+        Generator::new(self, rng, algo)
+    }
+
+    /// Builds multi-class generator with random number generator and classes specified.
+    /// The radii of the classes replace the radius of the builder and radius fields aren't supported.
+    pub fn build_with_classes<R>(self, rng: R, classes: Classes) -> ClassGenerator<R>
+    where
+        R: Rng,
+    {
+        self.try_build_with_classes(rng, classes)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds multi-class generator with random number generator and classes specified.
+    /// The radii of the classes replace the radius of the builder.
+    /// Fails if the builder has a radius field, as multi-class sampling doesn't support them.
+    pub fn try_build_with_classes<R>(
+        self,
+        rng: R,
+        classes: Classes,
+    ) -> Result<ClassGenerator<R>, Error>
+    where
+        R: Rng,
+    {
+        if self.radius_field.is_some() {
+            return Err(Error::UnsupportedRadiusField);
+        }
+        Ok(ClassGenerator::new(self, rng, classes))
+    }
+
+    /// Returns the builder of the rectangle at the offset with the dimensions, which is never periodic.
+    /// Points of the new builder are relative to the offset.
+    pub(crate) fn part(&self, offset: Vec2, dimensions: Vec2) -> Builder {
+        let radius_field = self.radius_field.clone().map(|field| {
+            let function = field.function;
+            RadiusField {
+                function: Arc::new(move |point: mint::Vector2<f32>| {
+                    function((Vec2::from(point) + offset).into())
+                }),
+                max_radius: field.max_radius,
+            }
+        });
+        let predicate = self.predicate.clone().map(|predicate| {
+            let function = predicate.function;
+            Predicate {
+                function: Arc::new(move |point: mint::Vector2<f32>| {
+                    function((Vec2::from(point) + offset).into())
+                }),
+            }
+        });
+        Builder {
+            radius: self.radius,
+            dimensions,
+            poisson_type: Type::Normal,
+            samples: None,
+            radius_field,
+            polygon: self.polygon.as_ref().map(|p| p.translated(-offset)),
+            predicate,
+        }
+    }
+
+    /// Builds generator of an infinite world split into chunks with world seed and algorithm specified.
+    /// The width and height of the builder are the ones of each chunk and should be at least twice the largest
+    /// radius. The type should be `Type::Normal` and polygons aren't supported.
+    /// Radius fields and predicates are given points in world coordinates.
+    pub fn build_chunked<R, A>(self, seed: u64, algo: A) -> ChunkGenerator<R, A>
+    where
+        R: Rng + SeedableRng,
+        A: Creator,
+    {
+        ChunkGenerator::new(self, seed, algo)
     }
 }
 
-/// Generates a Poisson disk sampling in a [0, 1)<sup>2</sup> area.
+/// Generates a Poisson disk sampling in a [0, width) × [0, height) area.
 #[derive(Clone, Debug)]
 pub struct Generator<R, A>
 where
@@ -141,7 +804,7 @@ where
 {
     poisson: Builder,
     rng: R,
-    _algo: PhantomData<A>,
+    algo: A,
 }
 
 impl<R, A> Generator<R, A>
@@ -149,18 +812,30 @@ where
     R: Rng,
     A: Creator,
 {
-    fn new(poisson: Builder, rng: R) -> Self {
-        Generator {
-            rng,
-            poisson,
-            _algo: PhantomData,
-        }
+    fn new(poisson: Builder, rng: R, algo: A) -> Self {
+        Generator { rng, poisson, algo }
     }
 
     /// Sets the radius of the generator.
+    /// The radius should be in (0, √(width² + height²) / 2] and not exceed the maximum radius of a radius field.
     pub fn set_radius(&mut self, radius: f32) {
-        assert!(0.0 < radius && radius <= 2f32.sqrt() / 2.0);
+        self.try_set_radius(radius)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Sets the radius of the generator.
+    /// Fails if the radius isn't in (0, √(width² + height²) / 2] or exceeds the maximum radius of a radius field,
+    /// in which case the radius stays the same.
+    pub fn try_set_radius(&mut self, radius: f32) -> Result<(), Error> {
+        let mut max = self.poisson.dimensions.length() / 2.0;
+        if let Some(ref field) = self.poisson.radius_field {
+            max = max.min(field.max_radius);
+        }
+        check_radius(radius, max)?;
         self.poisson.radius = radius;
+        // The amount of samples was only given to derive the radius from.
+        self.poisson.samples = None;
+        Ok(())
     }
 
     /// Returns the radius of the generator.
@@ -168,15 +843,143 @@ where
         self.poisson.radius
     }
 
+    /// Returns the largest radius any sample of the generator can have.
+    pub fn max_radius(&self) -> f32 {
+        self.poisson.max_radius()
+    }
+
+    /// Returns the width and height of the area the generator samples.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.poisson.dimensions.into()
+    }
+
     /// Returns the type of the generator.
     pub fn poisson_type(&self) -> Type {
         self.poisson.poisson_type
     }
 
     /// Generates a Poisson disk sampling.
-    pub fn generate(self) -> Vec<mint::Vector2<f32>> {
-        self.into_iter().collect()
+    pub fn generate(self) -> Sampling {
+        let poisson = self.poisson.clone();
+        Sampling::new(&poisson, self)
     }
+
+    /// Generates a Poisson disk sampling with exactly the amount of samples and returns it with the radius used.
+    ///
+    /// The radius is searched for by generating with copies of the same random number generator and different radii,
+    /// starting from the radius of the generator.
+    /// If no radius gives exactly the amount of samples, the closest sampling with more samples is trimmed by
+    /// removing the samples closest to their neighbours, which leaves it slightly less than maximal.
+    /// Fails if the amount of samples is 0 or the sampled area is too small for that many samples.
+    pub fn generate_exact(self, samples: usize) -> Result<(Sampling, f32), Error>
+    where
+        R: Clone,
+    {
+        if samples == 0 {
+            return Err(Error::ZeroSamples);
+        }
+        let mut max = self.poisson.dimensions.length() / 2.0;
+        if let Some(ref field) = self.poisson.radius_field {
+            max = max.min(field.max_radius);
+        }
+        let generate = |radius: f32| {
+            let poisson = Builder {
+                radius,
+                samples: None,
+                ..self.poisson.clone()
+            };
+            Generator::new(poisson, self.rng.clone(), self.algo).generate()
+        };
+
+        // Largest radius with too many samples and smallest radius with too few of them.
+        let mut more: Option<(f32, Sampling)> = None;
+        let mut less: Option<(f32, Sampling)> = None;
+        let mut radius = self.poisson.radius;
+        for _ in 0..EXACT_SEARCH_STEPS {
+            let sampling = generate(radius);
+            let count = sampling.len();
+            if count == samples {
+                return Ok((sampling, radius));
+            }
+            // Amount of samples is roughly inversely proportional to the squared radius.
+            let estimate = radius * (count as f32 / samples as f32).sqrt();
+            if count > samples {
+                if more.iter().all(|&(r, _)| r < radius) {
+                    more = Some((radius, sampling));
+                }
+            } else if less.iter().all(|&(r, _)| radius < r) {
+                less = Some((radius, sampling));
+            }
+            // Smaller radii only make the grids larger once the polygon or predicate leave no room for any samples.
+            if count == 0 {
+                break;
+            }
+            radius = match (&more, &less) {
+                (Some((low, _)), Some((high, _))) => {
+                    if high / low < 1.0 + 1e-5 {
+                        break;
+                    }
+                    (low * high).sqrt()
+                }
+                _ => estimate.max(radius / 2.0).min(2.0 * radius).min(max),
+            };
+        }
+        match (more, less) {
+            (Some((radius, sampling)), _) => Ok((trim(&self.poisson, sampling, samples), radius)),
+            (None, Some((_, sampling))) => Err(Error::TooManySamples {
+                requested: samples,
+                max: sampling.len(),
+            }),
+            (None, None) => unreachable!("At least one sampling is generated."),
+        }
+    }
+}
+
+/// Amount of samplings generated at most while searching for the radius giving an exact amount of samples.
+const EXACT_SEARCH_STEPS: usize = 40;
+
+/// Removes samples until the amount remains, starting from the ones closest to their neighbours.
+fn trim(poisson: &Builder, sampling: Sampling, samples: usize) -> Sampling {
+    let mut order = (0..sampling.len())
+        .map(|i| {
+            let nearest = sampling
+                .nearest(sampling[i], 2)
+                .into_iter()
+                .find(|&j| j != i);
+            let distance = nearest.map_or(f32::INFINITY, |j| {
+                sqdist(
+                    sampling[i].into(),
+                    sampling[j].into(),
+                    poisson.dimensions,
+                    poisson.poisson_type,
+                )
+            });
+            (distance, i, nearest)
+        })
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut removed = vec![false; sampling.len()];
+    let mut excess = sampling.len() - samples;
+    // Removing only one sample of each close pair first keeps the other one from being removed needlessly.
+    for &(_, i, nearest) in &order {
+        if excess > 0 && nearest.iter().all(|&j| !removed[j]) {
+            removed[i] = true;
+            excess -= 1;
+        }
+    }
+    for &(_, i, _) in &order {
+        if excess > 0 && !removed[i] {
+            removed[i] = true;
+            excess -= 1;
+        }
+    }
+    let kept = sampling
+        .iter()
+        .zip(removed)
+        .filter(|&(_, r)| !r)
+        .map(|(&s, _)| s)
+        .collect::<Vec<_>>();
+    Sampling::new(poisson, kept)
 }
 
 impl<R, A> IntoIterator for Generator<R, A>
@@ -190,13 +993,14 @@ where
     fn into_iter(self) -> Self::IntoIter {
         PoissonIter {
             rng: self.rng,
-            algo: A::create(&self.poisson),
+            algo: self.algo.create(&self.poisson),
             poisson: self.poisson,
         }
     }
 }
 
 /// Iterator for generating a Poisson disk sampling.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone)]
 pub struct PoissonIter<R, A>
 where
@@ -234,6 +1038,21 @@ where
         self.poisson.radius
     }
 
+    /// Returns the largest radius any sample of the generator can have.
+    pub fn max_radius(&self) -> f32 {
+        self.poisson.max_radius()
+    }
+
+    /// Returns the radius of a sample at the given point.
+    pub fn radius_at(&self, point: mint::Vector2<f32>) -> f32 {
+        self.poisson.radius_at(point)
+    }
+
+    /// Returns the width and height of the area the generator samples.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.poisson.dimensions.into()
+    }
+
     /// Returns the type of the generator.
     pub fn poisson_type(&self) -> Type {
         self.poisson.poisson_type
@@ -244,8 +1063,22 @@ where
         self.algo.restrict(value);
     }
 
+    /// Restricts the poisson algorithm with arbitrary sample and grows the sampling outward from it.
+    /// Only the Bridson algorithm grows the sampling from earlier samples, for the others this is the same as
+    /// `restrict`.
+    /// Once seeded, Bridson only grows the sampling from the seeds, so parts of the area they can't reach stay empty
+    /// unless the gaps are filled.
+    pub fn seed(&mut self, value: mint::Vector2<f32>) {
+        self.algo.seed(value);
+    }
+
     /// Checks legality of sample for current distribution.
     pub fn stays_legal(&self, value: mint::Vector2<f32>) -> bool {
         self.algo.stays_legal(&self.poisson, value)
     }
+
+    /// Returns the state of the algorithm, such as the statistics of the Ebeida algorithm.
+    pub fn algorithm(&self) -> &A {
+        &self.algo
+    }
 }
@@ -0,0 +1,329 @@
+//! Module that contains multi-class Poisson disk sampling where each pair of sample classes has its own radius.
+
+use glam::Vec2;
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::utils::*;
+use crate::{Builder, Type};
+
+/// Radii between each pair of sample classes.
+///
+/// Samples of classes `i` and `j` stay at least `2 * radius(i, j)` apart.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Classes {
+    radii: Vec<f32>,
+    count: usize,
+}
+
+impl Classes {
+    /// New classes with the radius between each pair of classes specified.
+    /// The matrix should be square and symmetric with all radii larger than 0.
+    pub fn new(radii: Vec<Vec<f32>>) -> Self {
+        let count = radii.len();
+        assert!(count > 0);
+        assert!(radii.iter().all(|row| row.len() == count));
+        for (i, row) in radii.iter().enumerate() {
+            for (j, &radius) in row.iter().enumerate() {
+                assert!(radius > 0.0);
+                assert_eq!(radius, radii[j][i]);
+            }
+        }
+        Classes {
+            radii: radii.into_iter().flatten().collect(),
+            count,
+        }
+    }
+
+    /// New classes with the radius within each class specified.
+    /// Radii between different classes are chosen so that the samples of every group of classes with radius
+    /// larger than or equal to some bound form a Poisson disk sampling too.
+    /// All radii should be larger than 0.
+    /// Based on Wei, Li-Yi. "Multi-class blue noise sampling." ACM Transactions on Graphics (TOG) 29.4 (2010): 79.
+    pub fn from_radii(radii: &[f32]) -> Self {
+        let count = radii.len();
+        assert!(count > 0);
+        assert!(radii.iter().all(|&r| r > 0.0));
+        let mut matrix = vec![0.0; count * count];
+        for i in 0..count {
+            for j in 0..count {
+                matrix[i * count + j] = if i == j {
+                    radii[i]
+                } else {
+                    // The smallest group containing both classes has the combined density of its classes.
+                    let bound = radii[i].min(radii[j]);
+                    let density: f32 = radii
+                        .iter()
+                        .filter(|&&r| r >= bound)
+                        .map(|r| r.powi(-2))
+                        .sum();
+                    density.sqrt().recip()
+                };
+            }
+        }
+        Classes {
+            radii: matrix,
+            count,
+        }
+    }
+
+    /// Returns the amount of classes.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the radius between the two classes.
+    pub fn radius(&self, class1: usize, class2: usize) -> f32 {
+        assert!(class1 < self.count && class2 < self.count);
+        self.radii[class1 * self.count + class2]
+    }
+
+    fn min_radius(&self) -> f32 {
+        self.radii.iter().cloned().fold(f32::MAX, f32::min)
+    }
+
+    fn max_radius(&self) -> f32 {
+        self.radii.iter().cloned().fold(0.0, f32::max)
+    }
+}
+
+/// Generates a multi-class Poisson disk sampling in a [0, width) × [0, height) area.
+#[derive(Clone, Debug)]
+pub struct ClassGenerator<R>
+where
+    R: Rng,
+{
+    poisson: Builder,
+    classes: Classes,
+    rng: R,
+}
+
+impl<R> ClassGenerator<R>
+where
+    R: Rng,
+{
+    pub(crate) fn new(mut poisson: Builder, rng: R, classes: Classes) -> Self {
+        poisson.radius = classes.min_radius();
+        ClassGenerator {
+            poisson,
+            classes,
+            rng,
+        }
+    }
+
+    /// Returns the classes of the generator.
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    /// Returns the width and height of the area the generator samples.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.poisson.dimensions.into()
+    }
+
+    /// Returns the type of the generator.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson.poisson_type
+    }
+
+    /// Generates a multi-class Poisson disk sampling as pairs of class and sample.
+    pub fn generate(self) -> Vec<(usize, mint::Vector2<f32>)> {
+        self.into_iter().collect()
+    }
+}
+
+impl<R> IntoIterator for ClassGenerator<R>
+where
+    R: Rng,
+{
+    type Item = (usize, mint::Vector2<f32>);
+    type IntoIter = ClassIter<R>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let grid = Grid::with_max_radius(&self.poisson, self.classes.max_radius());
+        let spacing = grid.cell();
+        let free = (0..grid.cells())
+            .map(|cell| {
+                decode(cell, grid.side())
+                    .expect("Because we are decoding index within grid this should work.")
+            })
+            .filter(|&index| {
+                let (min, max) = cell_bounds(index, spacing);
+                self.poisson.overlaps(min, max)
+            })
+            .collect();
+        let count = self.classes.count();
+        ClassIter {
+            weights: (0..count)
+                .map(|c| self.classes.radius(c, c).powi(-2))
+                .collect(),
+            samples: vec![0; count],
+            failures: vec![0; count],
+            saturated: vec![false; count],
+            grid,
+            free,
+            outside: vec![],
+            poisson: self.poisson,
+            classes: self.classes,
+            rng: self.rng,
+        }
+    }
+}
+
+/// Iterator for generating a multi-class Poisson disk sampling.
+///
+/// Each sample is thrown for the class which has the least samples relative to its natural density, so the
+/// classes stay in proportion until they run out of space one by one.
+/// Based on Wei, Li-Yi. "Multi-class blue noise sampling." ACM Transactions on Graphics (TOG) 29.4 (2010): 79.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+pub struct ClassIter<R>
+where
+    R: Rng,
+{
+    poisson: Builder,
+    classes: Classes,
+    rng: R,
+    grid: Grid<(Vec2, usize)>,
+    free: Vec<[i32; 2]>,
+    outside: Vec<(Vec2, usize)>,
+    weights: Vec<f32>,
+    samples: Vec<usize>,
+    failures: Vec<usize>,
+    saturated: Vec<bool>,
+}
+
+impl<R> Iterator for ClassIter<R>
+where
+    R: Rng,
+{
+    type Item = (usize, mint::Vector2<f32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.free.is_empty() {
+            let (samples, weights) = (&self.samples, &self.weights);
+            let class = (0..self.classes.count())
+                .filter(|&c| !self.saturated[c])
+                .map(|c| (c, samples[c] as f32 / weights[c]))
+                .fold(None, |best: Option<(usize, f32)>, cur| match best {
+                    Some(best) if best.1 <= cur.1 => Some(best),
+                    _ => Some(cur),
+                })?
+                .0;
+            let free = self.rng.sample(Uniform::new(0, self.free.len()));
+            let index = self.free[free];
+            let sample = choose_random_sample(&mut self.rng, &self.grid, index, 0);
+            if self.poisson.contains(sample)
+                && is_class_disk_free(
+                    &self.grid,
+                    &self.poisson,
+                    &self.classes,
+                    index,
+                    class,
+                    sample,
+                    &self.outside,
+                )
+            {
+                self.grid
+                    .insert(index, (sample, class))
+                    .expect("Indexing base grid by free index failed.");
+                self.free.swap_remove(free);
+                self.samples[class] += 1;
+                self.failures[class] = 0;
+                return Some((class, sample.into()));
+            }
+            // Once throws fail more often than there are free cells the class is considered full.
+            self.failures[class] += 1;
+            if self.failures[class] > self.free.len() {
+                self.saturated[class] = true;
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Calculating upper bound should work because each free cell can fit at most one more sample.
+        (0, Some(self.free.len()))
+    }
+}
+
+impl<R> ClassIter<R>
+where
+    R: Rng,
+{
+    /// Returns the classes of the generator.
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    /// Returns the width and height of the area the generator samples.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.poisson.dimensions.into()
+    }
+
+    /// Returns the type of the generator.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson.poisson_type
+    }
+
+    /// Restricts the poisson algorithm with arbitrary sample of the class.
+    pub fn restrict(&mut self, class: usize, value: mint::Vector2<f32>) {
+        assert!(class < self.classes.count());
+        let sample: Vec2 = value.into();
+        let index = sample_to_index(&sample, self.grid.cell());
+        if self.grid.insert(index, (sample, class)).is_ok() {
+            let side = self.grid.side();
+            let wrapped = [
+                index[0].rem_euclid(side[0] as i32),
+                index[1].rem_euclid(side[1] as i32),
+            ];
+            self.free.retain(|&i| i != wrapped);
+        } else {
+            self.outside.push((sample, class));
+        }
+    }
+
+    /// Restricts the poisson algorithm with arbitrary sample of the class.
+    /// Multi-class sampling doesn't grow the sampling from earlier samples, so this is the same as `restrict`.
+    pub fn seed(&mut self, class: usize, value: mint::Vector2<f32>) {
+        self.restrict(class, value);
+    }
+
+    /// Checks legality of sample of the class for current distribution.
+    pub fn stays_legal(&self, class: usize, value: mint::Vector2<f32>) -> bool {
+        assert!(class < self.classes.count());
+        let sample: Vec2 = value.into();
+        let index = sample_to_index(&sample, self.grid.cell());
+        is_class_disk_free(
+            &self.grid,
+            &self.poisson,
+            &self.classes,
+            index,
+            class,
+            sample,
+            &self.outside,
+        )
+    }
+}
+
+#[test]
+fn radii_between_equal_classes_combine_density() {
+    let classes = Classes::from_radii(&[0.1, 0.1]);
+    assert_eq!(0.1, classes.radius(0, 0));
+    assert!((classes.radius(0, 1) - 0.1 / 2f32.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn radii_between_classes_are_symmetric_and_bounded() {
+    let classes = Classes::from_radii(&[0.04, 0.01, 0.02]);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(classes.radius(i, j), classes.radius(j, i));
+            assert!(classes.radius(i, j) <= classes.radius(i, i).min(classes.radius(j, j)));
+        }
+    }
+    // The two sparsest classes together have the density of 1 / 0.04² + 1 / 0.02².
+    let expected = (0.04f32.powi(-2) + 0.02f32.powi(-2)).sqrt().recip();
+    assert!((classes.radius(0, 2) - expected).abs() < 1e-6);
+}
@@ -0,0 +1,133 @@
+//! Module that contains generation split over threads with rayon.
+//!
+//! The area is split into tiles that are split into four phases by the parity of their coordinates.
+//! Tiles of the same phase never touch, so they are generated in parallel, each restricted by the samples of its
+//! neighbours of earlier phases. The samples stay apart across the borders of the tiles and the sampling is as
+//! maximal as the algorithm makes it.
+
+use glam::Vec2;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use crate::algorithm::Creator;
+use crate::sampling::Sampling;
+use crate::utils::{mix_seed, phase, sample_to_index, sqdist, Grid};
+use crate::{Generator, Type};
+
+/// Width and height of the tiles in radii, which is large enough for each tile to have hundreds of samples.
+const TILE_RADII: f32 = 64.0;
+
+impl<R, A> Generator<R, A>
+where
+    R: Rng + SeedableRng,
+    A: Creator,
+{
+    /// Generates a Poisson disk sampling on all threads of the rayon thread pool.
+    ///
+    /// Every tile of the area gets its own random number generator seeded from the one of the generator, so the
+    /// samples are the same for the same seed no matter the amount of threads, but differ from the ones of
+    /// `generate`. Areas too small to split are generated on the current thread.
+    pub fn generate_parallel(mut self) -> Sampling
+    where
+        A: Sync,
+    {
+        let dimensions = self.poisson.dimensions;
+        let size = (TILE_RADII * self.poisson.max_radius()).max(2.0 * self.poisson.max_radius());
+        let mut tiles = [0; 2];
+        for n in 0..2 {
+            tiles[n] = (dimensions[n] / size).floor().max(1.0) as i32;
+            // Phases only stay apart across the edges if the amount of tiles is even.
+            if self.poisson.poisson_type == Type::Periodic {
+                tiles[n] -= tiles[n] % 2;
+            }
+        }
+        if tiles.iter().product::<i32>() < 2 || tiles.contains(&0) {
+            return self.generate();
+        }
+        let tile = dimensions / Vec2::new(tiles[0] as f32, tiles[1] as f32);
+        let margin = Vec2::splat(2.0 * self.poisson.max_radius());
+        let seed = self.rng.gen::<u64>();
+        let poisson = &self.poisson;
+        let algo = self.algo;
+
+        let mut samples: Vec<Vec<Vec2>> = vec![vec![]; (tiles[0] * tiles[1]) as usize];
+        for current in 0..4 {
+            let indices = (0..tiles[1])
+                .flat_map(|y| (0..tiles[0]).map(move |x| [x, y]))
+                .filter(|&index| phase(index) == current)
+                .collect::<Vec<_>>();
+            let done = &samples;
+            let generated = indices
+                .par_iter()
+                .map(|&index| {
+                    let offset = Vec2::new(index[0] as f32, index[1] as f32) * tile;
+                    let generator = Generator::<R, A> {
+                        poisson: poisson.part(offset, tile),
+                        rng: R::seed_from_u64(mix_seed(seed, index)),
+                        algo,
+                    };
+                    let mut iter = generator.into_iter();
+                    for dy in -1..=1 {
+                        for dx in -1..=1 {
+                            let mut neighbour = [index[0] + dx, index[1] + dy];
+                            if poisson.poisson_type == Type::Periodic {
+                                neighbour = [
+                                    neighbour[0].rem_euclid(tiles[0]),
+                                    neighbour[1].rem_euclid(tiles[1]),
+                                ];
+                            } else if !(0..tiles[0]).contains(&neighbour[0])
+                                || !(0..tiles[1]).contains(&neighbour[1])
+                            {
+                                continue;
+                            }
+                            if phase(neighbour) >= current {
+                                continue;
+                            }
+                            // Neighbours across the edges of periodic areas are moved next to this tile.
+                            let wrap = Vec2::new(
+                                (index[0] + dx - neighbour[0]) as f32,
+                                (index[1] + dy - neighbour[1]) as f32,
+                            ) * tile;
+                            for &sample in &done[(neighbour[1] * tiles[0] + neighbour[0]) as usize]
+                            {
+                                let local = sample + wrap - offset;
+                                if (-margin).cmplt(local).all() && local.cmplt(tile + margin).all()
+                                {
+                                    iter.restrict(local.into());
+                                }
+                            }
+                        }
+                    }
+                    // Moving the samples from the tile to the whole area rounds them, which can push the ones right
+                    // at the edges out of the area.
+                    iter.map(|sample| Vec2::from(sample) + offset)
+                        .filter(|&sample| {
+                            Vec2::zero().cmple(sample).all() && sample.cmplt(dimensions).all()
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            for (index, generated) in indices.into_iter().zip(generated) {
+                samples[(index[1] * tiles[0] + index[0]) as usize] = generated;
+            }
+        }
+        // Rounding can also push samples a tiny bit too close to each other, so those are dropped.
+        let mut grid: Grid = Grid::new(poisson);
+        let mut kept = vec![];
+        for sample in samples.into_iter().flatten() {
+            let side = grid.side();
+            let [x, y] = sample_to_index(&sample, grid.cell());
+            let index = [x.min(side[0] as i32 - 1), y.min(side[1] as i32 - 1)];
+            let free = grid.neighbours(index).all(|&other| {
+                let radius = poisson.radius_at(sample.into()) + poisson.radius_at(other.into());
+                sqdist(sample, other, dimensions, poisson.poisson_type).sqrt() >= radius
+            });
+            if free {
+                grid.insert(index, sample)
+                    .expect("Because the sample is in the area, indexing it should work.");
+                kept.push(sample.into());
+            }
+        }
+        Sampling::new(poisson, kept)
+    }
+}
@@ -0,0 +1,218 @@
+//! Module that contains polygons which can be used to restrict the area samples are generated in.
+
+use glam::Vec2;
+
+/// Polygon with an outer ring and any amount of holes.
+///
+/// Rings are lists of vertices which are implicitly closed, so the last vertex connects back to the first one.
+/// Both clockwise and counter-clockwise rings are accepted.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+    rings: Vec<Vec<Vec2>>,
+}
+
+impl Polygon {
+    /// New polygon with the outer ring specified.
+    /// The ring should have at least 3 vertices.
+    pub fn new<I>(outer: I) -> Self
+    where
+        I: IntoIterator<Item = mint::Vector2<f32>>,
+    {
+        let outer = outer.into_iter().map(Vec2::from).collect::<Vec<_>>();
+        assert!(outer.len() >= 3);
+        Polygon { rings: vec![outer] }
+    }
+
+    /// Cuts a hole into the polygon.
+    /// The hole should have at least 3 vertices and lie inside the outer ring.
+    pub fn with_hole<I>(mut self, hole: I) -> Self
+    where
+        I: IntoIterator<Item = mint::Vector2<f32>>,
+    {
+        let hole = hole.into_iter().map(Vec2::from).collect::<Vec<_>>();
+        assert!(hole.len() >= 3);
+        self.rings.push(hole);
+        self
+    }
+
+    /// Returns the vertices of the outer ring.
+    pub fn outer(&self) -> impl Iterator<Item = mint::Vector2<f32>> + '_ {
+        self.rings[0].iter().map(|&v| v.into())
+    }
+
+    /// Returns the vertices of each hole.
+    pub fn holes(&self) -> impl Iterator<Item = Vec<mint::Vector2<f32>>> + '_ {
+        self.rings[1..]
+            .iter()
+            .map(|ring| ring.iter().map(|&v| v.into()).collect())
+    }
+
+    /// Checks if the point is inside the polygon and outside all of its holes.
+    pub fn contains(&self, point: mint::Vector2<f32>) -> bool {
+        self.contains_point(point.into())
+    }
+
+    pub(crate) fn contains_point(&self, point: Vec2) -> bool {
+        // Even-odd rule works for the holes too, as long as they are inside the outer ring.
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if (a.y() > point.y()) != (b.y() > point.y()) {
+                let x = a.x() + (point.y() - a.y()) / (b.y() - a.y()) * (b.x() - a.x());
+                if point.x() < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Checks if the polygon and the axis-aligned rectangle have any area in common.
+    pub(crate) fn overlaps(&self, min: Vec2, max: Vec2) -> bool {
+        // If none of the edges pass through the rectangle, it is either completely inside or completely outside.
+        self.edges()
+            .any(|(a, b)| segment_intersects_rect(a, b, min, max))
+            || self.contains_point((min + max) / 2.0)
+    }
+
+    /// Returns the vertices of the polygon inside of the axis-aligned rectangle and the points where its edges cross
+    /// the sides of the rectangle.
+    pub(crate) fn crossings(&self, min: Vec2, max: Vec2) -> Vec<Vec2> {
+        self.edges()
+            .filter_map(|(a, b)| clip_segment(a, b, min, max).map(|t| (a, b, t)))
+            .flat_map(|(a, b, (enter, exit))| vec![a + (b - a) * enter, a + (b - a) * exit])
+            .collect()
+    }
+
+    /// Returns the polygon moved by the offset.
+    pub(crate) fn translated(&self, offset: Vec2) -> Polygon {
+        Polygon {
+            rings: self
+                .rings
+                .iter()
+                .map(|ring| ring.iter().map(|&v| v + offset).collect())
+                .collect(),
+        }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.rings.iter().flat_map(|ring| {
+            ring.iter()
+                .zip(ring.iter().cycle().skip(1))
+                .map(|(&a, &b)| (a, b))
+        })
+    }
+}
+
+fn segment_intersects_rect(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> bool {
+    clip_segment(a, b, min, max).is_some()
+}
+
+/// Clips the segment against the rectangle as in the Liang–Barsky algorithm.
+/// Returns the parameters along the segment where it enters and exits the rectangle, if it passes through it.
+fn clip_segment(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> Option<(f32, f32)> {
+    let diff = b - a;
+    let (mut enter, mut exit) = (0f32, 1f32);
+    for n in 0..2 {
+        if diff[n] == 0.0 {
+            if a[n] < min[n] || a[n] > max[n] {
+                return None;
+            }
+        } else {
+            let t1 = (min[n] - a[n]) / diff[n];
+            let t2 = (max[n] - a[n]) / diff[n];
+            enter = enter.max(t1.min(t2));
+            exit = exit.min(t1.max(t2));
+        }
+    }
+    if enter <= exit {
+        Some((enter, exit))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+fn square_with_hole() -> Polygon {
+    Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [1.0, 1.0].into(),
+        [0.0, 1.0].into(),
+    ])
+    .with_hole(vec![
+        [0.25, 0.25].into(),
+        [0.25, 0.75].into(),
+        [0.75, 0.75].into(),
+        [0.75, 0.25].into(),
+    ])
+}
+
+#[test]
+fn polygon_contains_works() {
+    let polygon = square_with_hole();
+    assert!(polygon.contains([0.1, 0.1].into()));
+    assert!(polygon.contains([0.9, 0.5].into()));
+    assert!(!polygon.contains([0.5, 0.5].into()));
+    assert!(!polygon.contains([1.1, 0.5].into()));
+    assert!(!polygon.contains([-0.1, -0.1].into()));
+}
+
+#[test]
+fn polygon_overlaps_works() {
+    let polygon = square_with_hole();
+    // Inside the hole.
+    assert!(!polygon.overlaps(Vec2::new(0.4, 0.4), Vec2::new(0.6, 0.6)));
+    // Crossing the edge of the hole.
+    assert!(polygon.overlaps(Vec2::new(0.2, 0.4), Vec2::new(0.3, 0.6)));
+    // Completely inside.
+    assert!(polygon.overlaps(Vec2::new(0.05, 0.05), Vec2::new(0.1, 0.1)));
+    // Completely outside.
+    assert!(!polygon.overlaps(Vec2::new(1.5, 1.5), Vec2::new(2.0, 2.0)));
+    // Containing the whole polygon.
+    assert!(polygon.overlaps(Vec2::new(-1.0, -1.0), Vec2::new(2.0, 2.0)));
+}
+
+#[test]
+fn segment_intersection_works() {
+    let (min, max) = (Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+    assert!(segment_intersects_rect(
+        Vec2::new(-1.0, 0.5),
+        Vec2::new(2.0, 0.5),
+        min,
+        max
+    ));
+    assert!(segment_intersects_rect(
+        Vec2::new(0.5, 0.5),
+        Vec2::new(0.6, 0.6),
+        min,
+        max
+    ));
+    assert!(!segment_intersects_rect(
+        Vec2::new(-1.0, 2.0),
+        Vec2::new(2.0, 1.5),
+        min,
+        max
+    ));
+    assert!(!segment_intersects_rect(
+        Vec2::new(1.5, -1.0),
+        Vec2::new(1.5, 2.0),
+        min,
+        max
+    ));
+}
+
+#[test]
+fn polygon_crossings_works() {
+    let polygon = square_with_hole();
+    // Crossing the edge of the hole at (0.25, 0.4) and (0.25, 0.6).
+    let crossings = polygon.crossings(Vec2::new(0.2, 0.4), Vec2::new(0.3, 0.6));
+    assert!(crossings.contains(&Vec2::new(0.25, 0.4)));
+    assert!(crossings.contains(&Vec2::new(0.25, 0.6)));
+    // Around the corner of the hole.
+    let crossings = polygon.crossings(Vec2::new(0.7, 0.7), Vec2::new(0.8, 0.8));
+    assert!(crossings.contains(&Vec2::new(0.75, 0.75)));
+    assert!(polygon
+        .crossings(Vec2::new(0.05, 0.05), Vec2::new(0.1, 0.1))
+        .is_empty());
+}
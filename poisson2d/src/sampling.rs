@@ -0,0 +1,202 @@
+//! Module that contains generated samplings which can be queried for samples near a point or in an area.
+
+use std::fmt;
+use std::ops::Deref;
+
+use glam::Vec2;
+
+use crate::delaunay::Triangulation;
+use crate::utils::*;
+use crate::{check_radius, Builder, Error, Type};
+
+/// Poisson disk sampling which can be queried for samples near a point or in an area.
+///
+/// Queries return indices of the samples, which can be used to index the sampling.
+/// For `Type::Periodic` distances and areas wrap around the edges of the sampled area.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+pub struct Sampling {
+    samples: Vec<mint::Vector2<f32>>,
+    grid: Grid<usize>,
+    outside: Vec<usize>,
+    dimensions: Vec2,
+    poisson_type: Type,
+}
+
+impl Sampling {
+    /// New sampling consisting of the samples in the area of the builder.
+    /// Samples outside of the area are allowed, but for `Type::Periodic` they are treated as if they were wrapped into it.
+    /// The radius of the builder should be in (0, √(width² + height²) / 2].
+    pub fn new<I>(poisson: &Builder, samples: I) -> Self
+    where
+        I: IntoIterator<Item = mint::Vector2<f32>>,
+    {
+        Sampling::try_new(poisson, samples).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New sampling consisting of the samples in the area of the builder.
+    /// Samples outside of the area are allowed, but for `Type::Periodic` they are treated as if they were wrapped into it.
+    /// Fails if the radius of the builder isn't in (0, √(width² + height²) / 2], such as for `Builder::default()`.
+    pub fn try_new<I>(poisson: &Builder, samples: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = mint::Vector2<f32>>,
+    {
+        check_radius(poisson.radius, poisson.dimensions.length() / 2.0)?;
+        let samples = samples.into_iter().collect::<Vec<_>>();
+        let mut grid = Grid::new(poisson);
+        let mut outside = vec![];
+        for (i, &sample) in samples.iter().enumerate() {
+            let index = sample_to_index(&sample.into(), grid.cell());
+            if let Err(i) = grid.insert(index, i) {
+                outside.push(i);
+            }
+        }
+        Ok(Sampling {
+            samples,
+            grid,
+            outside,
+            dimensions: poisson.dimensions,
+            poisson_type: poisson.poisson_type,
+        })
+    }
+
+    /// Returns the width and height of the area of the sampling.
+    pub fn dimensions(&self) -> mint::Vector2<f32> {
+        self.dimensions.into()
+    }
+
+    /// Returns the type of the sampling.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Returns indices of all samples at most the distance away from the point.
+    pub fn within(&self, point: mint::Vector2<f32>, distance: f32) -> Vec<usize> {
+        let point = self.wrap(point.into());
+        let reach = Vec2::splat(distance);
+        self.grid
+            .region(point - reach, point + reach)
+            .chain(&self.outside)
+            .cloned()
+            .filter(|&i| self.sqdist(point, i) <= distance * distance)
+            .collect()
+    }
+
+    /// Returns indices of the samples nearest to the point ordered by their distance to it.
+    /// There are less than the requested amount of indices only if the sampling has less samples.
+    pub fn nearest(&self, point: mint::Vector2<f32>, amount: usize) -> Vec<usize> {
+        let amount = amount.min(self.samples.len());
+        if amount == 0 {
+            return vec![];
+        }
+        // All samples within a distance are known, so the search area grows until it contains enough of them or the
+        // whole area, after which every sample is a candidate.
+        let point = self.wrap(point.into());
+        let covers = |distance: f32| {
+            (0..2).all(|n| point[n] - distance <= 0.0 && self.dimensions[n] <= point[n] + distance)
+        };
+        let mut distance = self.grid.cell().max_element();
+        let mut result = loop {
+            if covers(distance) || !distance.is_finite() {
+                break (0..self.samples.len()).collect();
+            }
+            let result = self.within(point.into(), distance);
+            if result.len() >= amount {
+                break result;
+            }
+            distance *= 2.0;
+        };
+        result.sort_by(|&a, &b| self.sqdist(point, a).total_cmp(&self.sqdist(point, b)));
+        result.truncate(amount);
+        result
+    }
+
+    /// Returns indices of all samples in the axis-aligned rectangle with the corners specified.
+    pub fn in_rect(&self, min: mint::Vector2<f32>, max: mint::Vector2<f32>) -> Vec<usize> {
+        let (min, max): (Vec2, Vec2) = (min.into(), max.into());
+        let size = max - min;
+        let contains = |v: Vec2| {
+            (0..2).all(|n| match self.poisson_type {
+                Type::Periodic => (v[n] - min[n]).rem_euclid(self.dimensions[n]) <= size[n],
+                Type::Normal => min[n] <= v[n] && v[n] <= max[n],
+            })
+        };
+        self.grid
+            .region(min, max)
+            .chain(&self.outside)
+            .cloned()
+            .filter(|&i| contains(self.samples[i].into()))
+            .collect()
+    }
+
+    /// Builds the Delaunay triangulation and Voronoi cells of the sampling.
+    pub fn triangulate(&self) -> Triangulation {
+        Triangulation::new(self)
+    }
+
+    /// Moves the point into the area for `Type::Periodic`, as distances only wrap around the edges once.
+    fn wrap(&self, mut point: Vec2) -> Vec2 {
+        if let Type::Periodic = self.poisson_type {
+            for n in 0..2 {
+                point[n] = point[n].rem_euclid(self.dimensions[n]);
+            }
+        }
+        point
+    }
+
+    fn sqdist(&self, point: Vec2, index: usize) -> f32 {
+        sqdist(
+            point,
+            self.samples[index].into(),
+            self.dimensions,
+            self.poisson_type,
+        )
+    }
+}
+
+impl Deref for Sampling {
+    type Target = [mint::Vector2<f32>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.samples
+    }
+}
+
+impl PartialEq for Sampling {
+    fn eq(&self, other: &Self) -> bool {
+        // The grid is built from the samples, so it doesn't need to be compared.
+        self.samples == other.samples
+            && self.dimensions == other.dimensions
+            && self.poisson_type == other.poisson_type
+    }
+}
+
+impl fmt::Debug for Sampling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.samples.fmt(f)
+    }
+}
+
+impl From<Sampling> for Vec<mint::Vector2<f32>> {
+    fn from(sampling: Sampling) -> Self {
+        sampling.samples
+    }
+}
+
+impl IntoIterator for Sampling {
+    type Item = mint::Vector2<f32>;
+    type IntoIter = std::vec::IntoIter<mint::Vector2<f32>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.samples.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Sampling {
+    type Item = &'a mint::Vector2<f32>;
+    type IntoIter = std::slice::Iter<'a, mint::Vector2<f32>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.samples.iter()
+    }
+}
@@ -0,0 +1,92 @@
+use glam::Vec3;
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::algorithm::Bridson;
+use crate::spherical::{Algorithm, Builder, Creator};
+use crate::utils::cube::*;
+
+impl Creator for Bridson {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        Algo {
+            grid: CubeGrid::new(poisson.radius),
+            active_samples: vec![],
+            seeded: false,
+            success: 0,
+        }
+    }
+}
+
+/// Implementation for the Bridson algorithm on the surface of the sphere
+pub struct Algo {
+    grid: CubeGrid,
+    active_samples: Vec<Vec3>,
+    seeded: bool,
+    success: usize,
+}
+
+impl Algorithm for Algo {
+    fn next<R>(&mut self, poisson: &Builder, rng: &mut R) -> Option<mint::Vector3<f32>>
+    where
+        R: Rng,
+    {
+        if !self.seeded {
+            self.seeded = true;
+            // The sphere has no boundary, so a single sample grows into a sampling of the whole surface.
+            for _ in 0..self.grid.cells() {
+                let sample = random_direction(rng);
+                if self.insert_if_valid(poisson, sample) {
+                    return Some(sample.into());
+                }
+            }
+        }
+        while !self.active_samples.is_empty() {
+            let index = rng.sample(Uniform::new(0, self.active_samples.len()));
+            let cur = self.active_samples[index];
+            for _ in 0..30 {
+                let min = 2.0 * poisson.radius;
+                let max = 4.0 * poisson.radius;
+                let sample = random_point_annulus(rng, cur, min, max);
+                if self.insert_if_valid(poisson, sample) {
+                    return Some(sample.into());
+                }
+            }
+            self.active_samples.swap_remove(index);
+        }
+        None
+    }
+
+    fn size_hint(&self, _: &Builder) -> (usize, Option<usize>) {
+        // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
+        let upper = self.grid.cells().saturating_sub(self.success);
+        // The algorithm can stop as soon as it runs out of active samples, so no lower bound is known.
+        (0, Some(upper))
+    }
+
+    fn restrict(&mut self, sample: mint::Vector3<f32>) {
+        let sample = Vec3::from(sample).normalize();
+        self.success += 1;
+        self.grid.insert(sample);
+    }
+
+    fn stays_legal(&self, poisson: &Builder, sample: mint::Vector3<f32>) -> bool {
+        let sample = Vec3::from(sample).normalize();
+        let min = 2.0 * poisson.radius;
+        is_free(self.grid.neighbours(sample, min), sample, min)
+    }
+}
+
+impl Algo {
+    fn insert_if_valid(&mut self, poisson: &Builder, sample: Vec3) -> bool {
+        if self.stays_legal(poisson, sample.into()) {
+            self.active_samples.push(sample);
+            self.grid.insert(sample);
+            self.success += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::f32::consts::PI;
+
+use glam::{Vec2, Vec3};
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::algorithm::Ebeida;
+use crate::spherical::{Algorithm, Builder, Creator};
+use crate::utils::cube::*;
+use crate::utils::{each_combination, get_parent, Inplace};
+
+impl Creator for Ebeida {
+    type Algo = Algo;
+
+    fn create(&self, poisson: &Builder) -> Self::Algo {
+        let grid = CubeGrid::new(poisson.radius);
+        let indices = (0..grid.cells())
+            .map(|cell| grid.decode(cell))
+            .collect::<Vec<_>>();
+        // Indices of the deepest cells have to fit into `i32`.
+        let bits = 32 - (grid.side() as u32).leading_zeros();
+        let a = 0.3;
+        Algo {
+            a,
+            grid,
+            throws: (a * indices.len() as f64).ceil() as usize,
+            indices,
+            level: 0,
+            success: 0,
+            max_level: f32::MANTISSA_DIGITS.min(31 - bits) as usize,
+        }
+    }
+}
+
+/// Implementation for the Ebeida algorithm on the surface of the sphere
+pub struct Algo {
+    grid: CubeGrid,
+    indices: Vec<(usize, [i32; 2])>,
+    level: usize,
+    throws: usize,
+    success: usize,
+    max_level: usize,
+    a: f64,
+}
+
+impl Algorithm for Algo {
+    fn next<R>(&mut self, poisson: &Builder, rng: &mut R) -> Option<mint::Vector3<f32>>
+    where
+        R: Rng,
+    {
+        if self.indices.is_empty() {
+            return None;
+        }
+        while self.level < self.max_level {
+            while self.throws > 0 {
+                self.throws -= 1;
+                let index = rng.sample(Uniform::new(0, self.indices.len()));
+                let (face, cur) = self.indices[index];
+                let parent = get_parent(cur, self.level);
+                if !self.grid.is_empty_at(face, parent) {
+                    self.indices.swap_remove(index);
+                    if self.indices.is_empty() {
+                        return None;
+                    }
+                } else if let Some(sample) =
+                    choose_random_sample(rng, &self.grid, face, cur, self.level)
+                {
+                    if self.stays_legal(poisson, sample.into()) {
+                        self.grid.insert(sample);
+                        self.indices.swap_remove(index);
+                        self.success += 1;
+                        return Some(sample.into());
+                    }
+                }
+            }
+            self.subdivide(poisson);
+            if self.indices.is_empty() {
+                return None;
+            }
+            self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
+            self.level += 1;
+        }
+        // Cells left at the deepest level are tried at their corners until one of them fits.
+        while !self.indices.is_empty() {
+            let index = rng.sample(Uniform::new(0, self.indices.len()));
+            let (face, cur) = self.indices.swap_remove(index);
+            let sample = self.grid.direction(face, cur, Vec2::zero(), self.level);
+            if self.stays_legal(poisson, sample.into()) {
+                self.grid.insert(sample);
+                self.success += 1;
+                return Some(sample.into());
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self, poisson: &Builder) -> (usize, Option<usize>) {
+        // Calculating lower bound should work because we calculate how much area is left to be filled at worst case and
+        // how much cap can fill it at best case and just figure out how many fills are still needed.
+        // Projection shrinks the cells the most at the corners of the faces.
+        let spacing = self.grid.spacing(self.level);
+        let grid_area = (self.indices.len() as f32) * spacing * spacing * 3f32.powf(-1.5);
+        let cap_area = 2.0 * PI * (1.0 - (2.0 * poisson.radius).cos());
+        let lower = grid_area / cap_area;
+        let lower = (lower.floor() as usize).saturating_sub(1);
+        // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
+        let upper = self.grid.cells().saturating_sub(self.success);
+        (lower, Some(upper))
+    }
+
+    fn restrict(&mut self, sample: mint::Vector3<f32>) {
+        let sample = Vec3::from(sample).normalize();
+        self.success += 1;
+        self.grid.insert(sample);
+    }
+
+    fn stays_legal(&self, poisson: &Builder, sample: mint::Vector3<f32>) -> bool {
+        let sample = Vec3::from(sample).normalize();
+        let min = 2.0 * poisson.radius;
+        is_free(self.grid.neighbours(sample, min), sample, min)
+    }
+}
+
+impl Algo {
+    fn subdivide(&mut self, poisson: &Builder) {
+        let choices = &[0, 1];
+        let (grid, level) = (&self.grid, self.level);
+        self.indices.flat_map_inplace(|(face, i)| {
+            each_combination(choices)
+                .map(move |n| (face, [2 * i[0] + n[0], 2 * i[1] + n[1]]))
+                .filter(|&(face, c)| !covered(grid, poisson, face, c, level + 1))
+        });
+    }
+}
+
+fn covered(grid: &CubeGrid, poisson: &Builder, face: usize, index: [i32; 2], level: usize) -> bool {
+    let min = 2.0 * poisson.radius;
+    let (center, radius) = grid.bounds(face, index, level);
+    let near = grid.neighbours(center, min + radius).collect::<Vec<_>>();
+    grid.corners(face, index, level)
+        .all(|c| near.iter().any(|&&v| angle(v, c) < min))
+}
+
+#[test]
+fn last_level_keeps_sampling_remaining_cells() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let poisson = Builder::with_radius(0.05);
+    let count = |max_level: Option<usize>| {
+        let mut algo = Ebeida.create(&poisson);
+        if let Some(max_level) = max_level {
+            algo.max_level = max_level;
+        }
+        let mut rng = SmallRng::seed_from_u64(7);
+        std::iter::from_fn(|| algo.next(&poisson, &mut rng)).count()
+    };
+    let full = count(None);
+    let capped = count(Some(1));
+    assert!(
+        capped as f32 > 0.9 * full as f32,
+        "Only {} samples with one level while {} without a cap.",
+        capped,
+        full
+    );
+}
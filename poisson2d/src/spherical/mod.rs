@@ -0,0 +1,208 @@
+//! Module that contains Poisson disk sampling on the surface of the unit sphere.
+//!
+//! Distances are measured along great circles, so the radius is an angle in radians and samples are unit vectors.
+//! Both `algorithm::Bridson` and `algorithm::Ebeida` can generate samplings on the sphere.
+//! They always sample the way their default configurations do, as `BridsonConfig` and `EbeidaConfig` only apply to
+//! samplings of the plane, and their generation can't be saved and resumed with the `serde` feature.
+
+use std::f32::consts::PI;
+use std::fmt::Debug;
+
+use rand::Rng;
+
+use crate::utils::math::calc_max_radius;
+use crate::Error;
+
+mod bridson;
+mod ebeida;
+
+/// Builder for the spherical generator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Builder {
+    radius: f32,
+}
+
+impl Builder {
+    /// New Builder with radius specified as an angle in radians.
+    /// Samples stay at least twice the radius apart along great circles.
+    /// The radius should be in (0, π / 4]
+    pub fn with_radius(radius: f32) -> Self {
+        Builder::try_with_radius(radius).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder with radius specified as an angle in radians.
+    /// Fails if the radius isn't in (0, π / 4].
+    pub fn try_with_radius(radius: f32) -> Result<Self, Error> {
+        if 0.0 < radius && radius <= PI / 4.0 {
+            Ok(Builder { radius })
+        } else {
+            Err(Error::InvalidRadius {
+                radius,
+                max: PI / 4.0,
+            })
+        }
+    }
+
+    /// New Builder with approximate amount of samples and relative radius specified.
+    /// The amount of samples should be larger than 0.
+    /// The relative radius should be in (0, 1].
+    pub fn with_samples(samples: usize, relative: f32) -> Self {
+        Builder::try_with_samples(samples, relative).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// New Builder with approximate amount of samples and relative radius specified.
+    /// Fails if the amount of samples is 0 or the relative radius isn't in (0, 1].
+    pub fn try_with_samples(samples: usize, relative: f32) -> Result<Self, Error> {
+        if samples == 0 {
+            return Err(Error::ZeroSamples);
+        }
+        if !(0.0 < relative && relative <= 1.0) {
+            return Err(Error::InvalidRelativeRadius(relative));
+        }
+        // Samples are spread over the whole surface, which is close to planar when there are enough of them.
+        let radius = calc_max_radius(samples, 4.0 * PI) * relative;
+        Ok(Builder {
+            radius: radius.min(PI / 4.0),
+        })
+    }
+
+    /// Returns the radius of the generator.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Builds generator with random number generator and algorithm specified.
+    pub fn build<R, A>(self, rng: R, algo: A) -> Generator<R, A>
+    where
+        R: Rng,
+        A: Creator,
+    {
+        Generator {
+            poisson: self,
+            rng,
+            algo,
+        }
+    }
+}
+
+/// Constructs new instance of the algorithm for the surface of the sphere.
+pub trait Creator: Copy + Debug {
+    /// Algorithm instance associated with the trait
+    type Algo: Algorithm;
+
+    /// Creates new and empty algorithm instance.
+    fn create(&self, _: &Builder) -> Self::Algo;
+}
+
+/// Trait that describes a Poisson disk sampling generating algorithm for the surface of the sphere.
+pub trait Algorithm {
+    /// Generates new sample advancing the algorithm.
+    fn next<R>(&mut self, _: &Builder, _: &mut R) -> Option<mint::Vector3<f32>>
+    where
+        R: Rng;
+
+    /// Returns lower and upper bound of the amount of samples remaining for the algorithm to generate.
+    fn size_hint(&self, _: &Builder) -> (usize, Option<usize>);
+
+    /// Restricts the algorithm with an arbitrary sample.
+    fn restrict(&mut self, _: mint::Vector3<f32>);
+
+    /// Checks if a sample is valid for the Poisson disk sampling generated thus far by the algorithm.
+    fn stays_legal(&self, _: &Builder, _: mint::Vector3<f32>) -> bool;
+}
+
+/// Generates a Poisson disk sampling on the surface of the unit sphere.
+#[derive(Clone, Debug)]
+pub struct Generator<R, A>
+where
+    R: Rng,
+    A: Creator,
+{
+    poisson: Builder,
+    rng: R,
+    algo: A,
+}
+
+impl<R, A> Generator<R, A>
+where
+    R: Rng,
+    A: Creator,
+{
+    /// Returns the radius of the generator.
+    pub fn radius(&self) -> f32 {
+        self.poisson.radius
+    }
+
+    /// Generates a Poisson disk sampling.
+    pub fn generate(self) -> Vec<mint::Vector3<f32>> {
+        self.into_iter().collect()
+    }
+}
+
+impl<R, A> IntoIterator for Generator<R, A>
+where
+    R: Rng,
+    A: Creator,
+{
+    type Item = mint::Vector3<f32>;
+    type IntoIter = PoissonIter<R, A::Algo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PoissonIter {
+            rng: self.rng,
+            algo: self.algo.create(&self.poisson),
+            poisson: self.poisson,
+        }
+    }
+}
+
+/// Iterator for generating a Poisson disk sampling on the surface of the unit sphere.
+#[derive(Clone)]
+pub struct PoissonIter<R, A>
+where
+    R: Rng,
+    A: Algorithm,
+{
+    poisson: Builder,
+    rng: R,
+    algo: A,
+}
+
+impl<R, A> Iterator for PoissonIter<R, A>
+where
+    R: Rng,
+    A: Algorithm,
+{
+    type Item = mint::Vector3<f32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.algo.next(&self.poisson, &mut self.rng)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.algo.size_hint(&self.poisson)
+    }
+}
+
+impl<R, A> PoissonIter<R, A>
+where
+    R: Rng,
+    A: Algorithm,
+{
+    /// Returns the radius of the generator.
+    pub fn radius(&self) -> f32 {
+        self.poisson.radius
+    }
+
+    /// Restricts the poisson algorithm with arbitrary sample.
+    /// The sample doesn't have to be a unit vector, only its direction matters.
+    pub fn restrict(&mut self, value: mint::Vector3<f32>) {
+        self.algo.restrict(value);
+    }
+
+    /// Checks legality of sample for current distribution.
+    /// The sample doesn't have to be a unit vector, only its direction matters.
+    pub fn stays_legal(&self, value: mint::Vector3<f32>) -> bool {
+        self.algo.stays_legal(&self.poisson, value)
+    }
+}
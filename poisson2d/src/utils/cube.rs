@@ -0,0 +1,363 @@
+//! Cube map that divides the surface of the unit sphere into cells.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::{Vec2, Vec3};
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Grid of cells on the six faces of a cube which are projected onto the unit sphere through its center.
+///
+/// The projection maps great circles to straight lines, so the edges of every cell are great circle arcs.
+/// Cells are indexed by face and position on the face with both coordinates in [0, side).
+/// Like `Grid` it has one slot per cell and keeps further samples of the same cell aside in an overflow map that
+/// valid samplings never use.
+#[derive(Clone)]
+pub struct CubeGrid {
+    slots: Vec<Option<Vec3>>,
+    overflow: HashMap<usize, Vec<Vec3>>,
+    side: usize,
+    bounds: Vec<(Vec3, f32)>,
+    adjacent: Vec<[usize; 4]>,
+}
+
+impl CubeGrid {
+    pub fn new(radius: f32) -> CubeGrid {
+        // Face coordinates are in [-1, 1] and angles are never larger than distances on the face, so cells at most
+        // r√2 wide are at most 2r across and can contain at most one sample.
+        let side = ((2f32.sqrt() / radius).ceil() as usize).max(1);
+        let mut grid = CubeGrid {
+            slots: vec![None; 6 * side * side],
+            overflow: HashMap::new(),
+            side,
+            bounds: vec![],
+            adjacent: vec![],
+        };
+        // Base level cells are visited for every lookup, so their shape is only calculated once.
+        grid.bounds = (0..grid.cells())
+            .map(|cell| {
+                let (face, index) = grid.decode(cell);
+                grid.bounds(face, index, 0)
+            })
+            .collect();
+        grid.adjacent = (0..grid.cells())
+            .map(|cell| {
+                let (face, index) = grid.decode(cell);
+                let mut result = [0; 4];
+                for (r, &step) in result.iter_mut().zip(&[[1, 0], [-1, 0], [0, 1], [0, -1]]) {
+                    // Stepping over the edge of the face ends up on the neighbouring face.
+                    let next = [index[0] + step[0], index[1] + step[1]];
+                    let center = grid.direction(face, next, Vec2::splat(0.5), 0);
+                    let (face, index) = grid.locate(center);
+                    *r = grid.encode(face, index);
+                }
+                result
+            })
+            .collect();
+        grid
+    }
+
+    /// Returns the amount of base level cells along the edges of each face.
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn cells(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns width of cells on the given level of subdivision in face coordinates.
+    pub fn spacing(&self, level: usize) -> f32 {
+        2.0 / (self.side * 2usize.pow(level as u32)) as f32
+    }
+
+    pub fn decode(&self, cell: usize) -> (usize, [i32; 2]) {
+        let face = cell / (self.side * self.side);
+        let rest = cell % (self.side * self.side);
+        (face, [(rest / self.side) as i32, (rest % self.side) as i32])
+    }
+
+    fn encode(&self, face: usize, index: [i32; 2]) -> usize {
+        (face * self.side + index[0] as usize) * self.side + index[1] as usize
+    }
+
+    /// Finds the base level cell the direction points to.
+    pub fn locate(&self, direction: Vec3) -> (usize, [i32; 2]) {
+        let abs = direction.abs();
+        let axis = if abs.x() >= abs.y() && abs.x() >= abs.z() {
+            0
+        } else if abs.y() >= abs.z() {
+            1
+        } else {
+            2
+        };
+        let face = 2 * axis + if direction[axis] < 0.0 { 1 } else { 0 };
+        let uv = Vec2::new(direction[(axis + 1) % 3], direction[(axis + 2) % 3]) / abs[axis];
+        let last = self.side as i32 - 1;
+        let index = ((uv + Vec2::one()) / self.spacing(0)).floor();
+        (
+            face,
+            [
+                (index.x() as i32).clamp(0, last),
+                (index.y() as i32).clamp(0, last),
+            ],
+        )
+    }
+
+    /// Checks if the base level cell has no samples.
+    pub fn is_empty_at(&self, face: usize, index: [i32; 2]) -> bool {
+        self.slots[self.encode(face, index)].is_none()
+    }
+
+    pub fn insert(&mut self, sample: Vec3) {
+        let (face, index) = self.locate(sample);
+        let cell = self.encode(face, index);
+        match self.slots[cell] {
+            None => self.slots[cell] = Some(sample),
+            Some(_) => self.overflow.entry(cell).or_default().push(sample),
+        }
+    }
+
+    /// Returns the point of the face at the given offset in [0, 1]² from the corner of the cell on the given level of
+    /// subdivision.
+    pub fn position(&self, index: [i32; 2], offset: Vec2, level: usize) -> Vec2 {
+        let spacing = 2.0 / (self.side as f64 * 2f64.powi(level as i32));
+        let mut result = Vec2::zero();
+        for n in 0..2 {
+            result[n] = ((index[n] as f64 + offset[n] as f64) * spacing - 1.0) as f32;
+        }
+        result
+    }
+
+    /// Returns the unit vector pointing to the given offset of the cell on the given level of subdivision.
+    pub fn direction(&self, face: usize, index: [i32; 2], offset: Vec2, level: usize) -> Vec3 {
+        to_sphere(face, self.position(index, offset, level))
+    }
+
+    /// Returns unit vectors pointing to the corners of the cell on the given level of subdivision.
+    pub fn corners(
+        &self,
+        face: usize,
+        index: [i32; 2],
+        level: usize,
+    ) -> impl Iterator<Item = Vec3> + '_ {
+        [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]
+            .iter()
+            .map(move |&c| self.direction(face, index, Vec2::from(c), level))
+    }
+
+    /// Returns center of the cell and the largest angle between it and a point of the cell.
+    pub fn bounds(&self, face: usize, index: [i32; 2], level: usize) -> (Vec3, f32) {
+        let center = self.direction(face, index, Vec2::splat(0.5), level);
+        let radius = self
+            .corners(face, index, level)
+            .map(|c| angle(center, c))
+            .fold(0.0, f32::max);
+        (center, radius)
+    }
+
+    /// Iterates through all samples that can be closer to the point than the given angle.
+    pub fn neighbours(&self, point: Vec3, reach: f32) -> impl Iterator<Item = &Vec3> {
+        // Cells close enough to the point form a connected area, so they can be found by walking from the cell of
+        // the point to its neighbours, which also crosses the edges of the faces.
+        let (face, index) = self.locate(point);
+        let start = self.encode(face, index);
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut cells = vec![start];
+        let mut queue = vec![start];
+        while let Some(cell) = queue.pop() {
+            for &next in &self.adjacent[cell] {
+                if visited.contains(&next) {
+                    continue;
+                }
+                let (center, radius) = self.bounds[next];
+                if angle(point, center) <= reach + radius {
+                    visited.insert(next);
+                    cells.push(next);
+                    queue.push(next);
+                }
+            }
+        }
+        // The overflow is only visited if there is any, so that valid samplings only read the slots.
+        let overflow = if self.overflow.is_empty() {
+            None
+        } else {
+            Some(cells.clone())
+        };
+        let slots = cells
+            .into_iter()
+            .filter_map(move |cell| self.slots[cell].as_ref());
+        slots.chain(
+            overflow
+                .into_iter()
+                .flatten()
+                .filter_map(move |cell| self.overflow.get(&cell))
+                .flatten(),
+        )
+    }
+}
+
+/// Projects point of the face onto the unit sphere.
+pub fn to_sphere(face: usize, uv: Vec2) -> Vec3 {
+    let axis = face / 2;
+    let mut result = Vec3::zero();
+    result[axis] = [1.0, -1.0][face % 2];
+    result[(axis + 1) % 3] = uv.x();
+    result[(axis + 2) % 3] = uv.y();
+    result.normalize()
+}
+
+/// Returns the angle between the two directions, which is their great circle distance on the unit sphere.
+pub fn angle(v1: Vec3, v2: Vec3) -> f32 {
+    v1.cross(v2).length().atan2(v1.dot(v2))
+}
+
+/// Checks that the sample is at least the given angle away from all of the other samples.
+pub fn is_free<'a, I>(samples: I, sample: Vec3, min: f32) -> bool
+where
+    I: IntoIterator<Item = &'a Vec3>,
+{
+    samples.into_iter().all(|&t| angle(t, sample) >= min)
+}
+
+pub fn random_direction<R>(rng: &mut R) -> Vec3
+where
+    R: Rng,
+{
+    loop {
+        let result = Vec3::new(
+            rng.sample(StandardNormal),
+            rng.sample(StandardNormal),
+            rng.sample(StandardNormal),
+        );
+        if result.length_squared() > 0.0 {
+            return result.normalize();
+        }
+    }
+}
+
+/// Chooses random point of the cell on the given level of subdivision.
+/// Points are rejected in proportion to how much the projection shrinks them, so that accepted points are uniform
+/// on the sphere even though all cells on one level have the same size on the face.
+pub fn choose_random_sample<R>(
+    rng: &mut R,
+    grid: &CubeGrid,
+    face: usize,
+    index: [i32; 2],
+    level: usize,
+) -> Option<Vec3>
+where
+    R: Rng,
+{
+    let uv = grid.position(index, rng.gen(), level);
+    let density = (1.0 + uv.length_squared()).powf(-1.5);
+    if rng.gen::<f32>() < density {
+        Some(to_sphere(face, uv))
+    } else {
+        None
+    }
+}
+
+/// Chooses random point whose angle to the center is in [min, max) with all such points being equally likely.
+pub fn random_point_annulus<R>(rng: &mut R, center: Vec3, min: f32, max: f32) -> Vec3
+where
+    R: Rng,
+{
+    let max = max.min(std::f32::consts::PI);
+    let (cos_min, cos_max) = (min.cos(), max.cos());
+    let cos = cos_max + rng.gen::<f32>() * (cos_min - cos_max);
+    let sin = (1.0 - cos * cos).max(0.0).sqrt();
+    let azimuth = rng.gen::<f32>() * 2.0 * std::f32::consts::PI;
+    let abs = center.abs();
+    let other = if abs.x() <= abs.y() && abs.x() <= abs.z() {
+        Vec3::unit_x()
+    } else if abs.y() <= abs.z() {
+        Vec3::unit_y()
+    } else {
+        Vec3::unit_z()
+    };
+    let t1 = center.cross(other).normalize();
+    let t2 = center.cross(t1);
+    (center * cos + (t1 * azimuth.cos() + t2 * azimuth.sin()) * sin).normalize()
+}
+
+#[test]
+fn locating_direction_of_cell_finds_it() {
+    let grid = CubeGrid::new(0.1);
+    for cell in 0..grid.cells() {
+        let (face, index) = grid.decode(cell);
+        let (center, _) = grid.bounds(face, index, 0);
+        assert_eq!((face, index), grid.locate(center));
+    }
+}
+
+#[test]
+fn cells_are_small_enough_for_one_sample() {
+    let radius = 0.07;
+    let grid = CubeGrid::new(radius);
+    for cell in 0..grid.cells() {
+        let (face, index) = grid.decode(cell);
+        let (_, bound) = grid.bounds(face, index, 0);
+        assert!(bound <= radius);
+    }
+}
+
+#[test]
+fn neighbours_contain_all_close_samples() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let mut rng = SmallRng::seed_from_u64(42);
+    let radius = 0.1;
+    let mut grid = CubeGrid::new(radius);
+    let samples = (0..2000)
+        .map(|_| random_direction(&mut rng))
+        .collect::<Vec<_>>();
+    for &s in &samples {
+        grid.insert(s);
+    }
+    for _ in 0..200 {
+        let point = random_direction(&mut rng);
+        let found = grid
+            .neighbours(point, 2.0 * radius)
+            .cloned()
+            .collect::<Vec<_>>();
+        for &s in &samples {
+            if angle(s, point) < 2.0 * radius {
+                assert!(found.contains(&s));
+            }
+        }
+    }
+}
+
+#[test]
+fn neighbours_contain_samples_sharing_a_cell() {
+    let mut grid = CubeGrid::new(0.1);
+    let samples = [
+        Vec3::unit_z(),
+        Vec3::new(0.001, 0.0, 1.0).normalize(),
+        Vec3::new(0.0, 0.001, 1.0).normalize(),
+    ];
+    for &s in &samples {
+        grid.insert(s);
+    }
+    let found = grid
+        .neighbours(Vec3::unit_z(), 0.01)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(samples.len(), found.len());
+    for s in &samples {
+        assert!(found.contains(s));
+    }
+}
+
+#[test]
+fn random_point_annulus_stays_in_annulus() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let mut rng = SmallRng::seed_from_u64(42);
+    for _ in 0..1000 {
+        let center = random_direction(&mut rng);
+        let point = random_point_annulus(&mut rng, center, 0.2, 0.4);
+        let a = angle(center, point);
+        assert!((0.2 - 1e-4..=0.4 + 1e-4).contains(&a));
+    }
+}
@@ -1,16 +1,21 @@
 use std::f32::consts::PI;
 
-use crate::Type;
+use glam::Vec2;
 
-fn newton(samples: usize) -> usize {
+use crate::{Error, Type};
+
+/// Solves the amount of samples a periodic sampling would need to have to end up with the given amount of samples
+/// once the boundary is taken into account.
+/// The boundary factor is the perimeter of the area relative to the perimeter of a square with equal area.
+fn newton(samples: usize, boundary: f64) -> usize {
     const ALPHA: f64 = 1.0997;
     const BETA: f64 = -0.4999;
 
+    let alpha = ALPHA * boundary;
     let mut n = 1f64;
     for _ in 0..5 {
-        n = n
-            - (n + ALPHA * n.powf(BETA + 1.0) - samples as f64)
-                / (1.0 + ALPHA * (BETA + 1.0) * n.powf(BETA));
+        n -= (n + alpha * n.powf(BETA + 1.0) - samples as f64)
+            / (1.0 + alpha * (BETA + 1.0) * n.powf(BETA));
         if n < 1.0 {
             return 1;
         }
@@ -18,25 +23,61 @@ fn newton(samples: usize) -> usize {
     n as usize
 }
 
-/// Calculates radius from approximate samples and relative radius.
-/// The amount of samples should be larger than 0.
-/// The relative radius should be in (0, 1].
+const GAMMA: f32 = 1.0;
+// TODO: Replace 1.7320508 with 3f32.sqrt() once sqrt is const
+//       (see https://github.com/rust-lang/rust/issues/57241)
+const MAX_PACKING_DENSITY: f32 = 1.0 / 6.0 * PI * 1.7320508;
+const MAX_RADIUS: f32 = MAX_PACKING_DENSITY * GAMMA / PI;
+
+/// Calculates radius from approximate samples and relative radius for an area of the given dimensions.
+/// Fails if the amount of samples is 0 or the relative radius isn't in (0, 1].
 /// Based on Gamito, Manuel N., and Steve C. Maddock. "Accurate multidimensional Poisson-disk sampling." ACM Transactions on Graphics (TOG) 29.1 (2009): 8.
-pub fn calc_radius(samples: usize, relative: f32, poisson_type: Type) -> f32 {
+pub fn calc_radius(
+    samples: usize,
+    relative: f32,
+    dimensions: Vec2,
+    poisson_type: Type,
+) -> Result<f32, Error> {
     use crate::Type::*;
 
-    const GAMMA: f32 = 1.0;
-    // TODO: Replace 1.7320508 with 3f32.sqrt() once sqrt is const
-    //       (see https://github.com/rust-lang/rust/issues/57241)
-    const MAX_PACKING_DENSITY: f32 = 1.0 / 6.0 * PI * 1.7320508;
-    const MAX_RADIUS: f32 = MAX_PACKING_DENSITY * GAMMA / PI;
-
-    assert!(samples > 0);
-    assert!(0.0 < relative && relative <= 1.0);
+    if samples == 0 {
+        return Err(Error::ZeroSamples);
+    }
+    if !(0.0 < relative && relative <= 1.0) {
+        return Err(Error::InvalidRelativeRadius(relative));
+    }
 
+    let area = dimensions.x() * dimensions.y();
     let samples = match poisson_type {
         Periodic => samples,
-        Normal => newton(samples),
+        Normal => {
+            let boundary = (dimensions.x() + dimensions.y()) / (2.0 * area.sqrt());
+            newton(samples, boundary as f64)
+        }
     };
-    (MAX_RADIUS / (samples as f32)).sqrt() * relative
+    Ok(calc_max_radius(samples, area) * relative)
+}
+
+/// Calculates the largest radius the given amount of samples can have when packed into the given area.
+/// The amount of samples should be larger than 0.
+pub fn calc_max_radius(samples: usize, area: f32) -> f32 {
+    assert!(samples > 0);
+    (area * MAX_RADIUS / (samples as f32)).sqrt()
+}
+
+/// Calculates the amount of samples of a periodic sampling with the given radius and relative radius 1 in an area
+/// of the given dimensions, which is the inverse of `calc_radius`.
+/// The radius should be larger than 0.
+pub fn calc_samples(radius: f32, dimensions: Vec2) -> usize {
+    assert!(radius > 0.0);
+    let area = dimensions.x() * dimensions.y();
+    ((area * MAX_RADIUS / (radius * radius)) as usize).max(1)
+}
+
+#[test]
+fn calc_samples_inverts_calc_radius() {
+    let dimensions = Vec2::new(2.0, 0.5);
+    let radius = calc_radius(300, 1.0, dimensions, Type::Periodic).unwrap();
+    let samples = calc_samples(radius, dimensions);
+    assert!((299..=300).contains(&samples));
 }
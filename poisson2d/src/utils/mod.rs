@@ -1,237 +1,445 @@
 //! Helper functions that poisson2d uses.
 
+use std::collections::HashMap;
+
 use glam::Vec2;
-use modulo::Mod;
 use rand::Rng;
 
+use crate::multiclass::Classes;
 use crate::{Builder, Type};
 
+pub mod cube;
 pub mod math;
+pub mod void;
 
-#[derive(Clone)]
-pub struct Grid {
-    data: Vec<Vec<Vec2>>,
-    side: usize,
-    cell: f32,
+/// Grid over the sampled area with one slot per cell.
+///
+/// Cells of samplings are small enough to contain at most one sample, so further items in the same cell are kept
+/// aside in an overflow map that valid samplings never use.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Default)]
+pub struct Grid<T = Vec2> {
+    slots: Vec<Option<T>>,
+    overflow: HashMap<usize, Vec<T>>,
+    side: [usize; 2],
+    cell: Vec2,
+    reach: [i32; 2],
     poisson_type: Type,
 }
 
-impl Grid {
-    pub fn new(radius: f32, poisson_type: Type) -> Grid {
-        let cell = radius * 2.0 / 2f32.sqrt();
-        let side = (1.0 / cell) as usize;
+impl<T> Grid<T> {
+    pub fn new(poisson: &Builder) -> Grid<T> {
+        Grid::with_max_radius(poisson, poisson.max_radius())
+    }
+
+    pub fn with_max_radius(poisson: &Builder, max_radius: f32) -> Grid<T> {
+        // Cells of a zero radius would be infinitely many.
+        assert!(
+            poisson.radius > 0.0,
+            "Radius of the grid should be larger than 0."
+        );
+        let dimensions = poisson.dimensions;
+        // Cells are at most r√2 wide so that each of them can contain at most one sample.
+        let max_cell = poisson.radius * 2.0 / 2f32.sqrt();
+        let mut side = [0; 2];
+        let mut cell = Vec2::zero();
+        let mut reach = [0; 2];
+        for n in 0..2 {
+            side[n] = ((dimensions[n] / max_cell).ceil() as usize).max(1);
+            cell[n] = dimensions[n] / side[n] as f32;
+            // Samples can conflict with other samples up to twice the largest radius away.
+            reach[n] = (2.0 * max_radius / cell[n]).ceil() as i32;
+        }
         Grid {
             cell,
             side,
-            data: vec![vec![]; side.pow(2)],
-            poisson_type,
+            reach,
+            slots: (0..side[0] * side[1]).map(|_| None).collect(),
+            overflow: HashMap::new(),
+            poisson_type: poisson.poisson_type,
         }
     }
 
-    pub fn get(&self, index: Vec2) -> Option<&Vec<Vec2>> {
-        encode(&index, self.side, self.poisson_type).map(|t| &self.data[t])
+    /// Checks if the cell has no items, or returns `None` if the cell is outside of the grid.
+    pub fn is_empty_at(&self, index: [i32; 2]) -> Option<bool> {
+        encode(index, self.side, self.poisson_type).map(|t| self.slots[t].is_none())
     }
 
-    pub fn get_mut(&mut self, index: Vec2) -> Option<&mut Vec<Vec2>> {
-        encode(&index, self.side, self.poisson_type).map(move |t| &mut self.data[t])
+    /// Adds the item to the cell, or gives it back if the cell is outside of the grid.
+    pub fn insert(&mut self, index: [i32; 2], item: T) -> Result<(), T> {
+        match encode(index, self.side, self.poisson_type) {
+            Some(t) => {
+                match self.slots[t] {
+                    None => self.slots[t] = Some(item),
+                    Some(_) => self.overflow.entry(t).or_default().push(item),
+                }
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+
+    /// Iterates through all samples in cells that can contain samples conflicting with a sample in the given cell.
+    pub fn neighbours(&self, parent: [i32; 2]) -> impl Iterator<Item = &T> {
+        let [x, y] = parent;
+        let [reach_x, reach_y] = self.reach;
+        let cells = move || {
+            (x - reach_x..=x + reach_x)
+                .filter_map(move |x| self.wrap(0, x))
+                .flat_map(move |x| {
+                    (y - reach_y..=y + reach_y)
+                        .filter_map(move |y| self.wrap(1, y))
+                        .map(move |y| x * self.side[1] + y)
+                })
+        };
+        self.walk(cells)
+    }
+
+    /// Wraps the coordinate of a cell on the axis into the grid without going through floats.
+    fn wrap(&self, n: usize, coordinate: i32) -> Option<usize> {
+        let side = self.side[n] as i32;
+        match self.poisson_type {
+            Type::Periodic => Some(coordinate.rem_euclid(side) as usize),
+            Type::Normal if 0 <= coordinate && coordinate < side => Some(coordinate as usize),
+            Type::Normal => None,
+        }
+    }
+
+    /// Iterates through all items in cells overlapping the rectangle, visiting each cell at most once.
+    pub fn region(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = &T> {
+        let mut ranges = [(0, -1); 2];
+        for (n, range) in ranges.iter_mut().enumerate() {
+            let side = self.side[n] as i32;
+            let low = (min[n] / self.cell[n]).floor() as i32;
+            let high = (max[n] / self.cell[n]).floor() as i32;
+            *range = match self.poisson_type {
+                Type::Periodic if high - low + 1 >= side => (0, side - 1),
+                Type::Periodic => (low, high),
+                Type::Normal => (low.max(0), high.min(side - 1)),
+            };
+        }
+        let [(x0, x1), (y0, y1)] = ranges;
+        let cells = move || {
+            (x0..=x1)
+                .filter_map(move |x| self.wrap(0, x))
+                .flat_map(move |x| {
+                    (y0..=y1)
+                        .filter_map(move |y| self.wrap(1, y))
+                        .map(move |y| x * self.side[1] + y)
+                })
+        };
+        self.walk(cells)
+    }
+
+    /// Iterates through the items in the cells, visiting the overflow only if there is any, so that walking the cells
+    /// of valid samplings stays as cheap as reading the slots.
+    fn walk<F, I>(&self, cells: F) -> impl Iterator<Item = &T>
+    where
+        F: Fn() -> I,
+        I: Iterator<Item = usize>,
+    {
+        let slots = cells().filter_map(move |t| self.slots[t].as_ref());
+        let overflow = if self.overflow.is_empty() {
+            None
+        } else {
+            Some(cells().filter_map(move |t| self.overflow.get(&t)).flatten())
+        };
+        slots.chain(overflow.into_iter().flatten())
     }
 
     pub fn cells(&self) -> usize {
-        self.data.len()
+        self.slots.len()
     }
 
-    pub fn side(&self) -> usize {
+    pub fn side(&self) -> [usize; 2] {
         self.side
     }
 
-    pub fn cell(&self) -> f32 {
+    pub fn cell(&self) -> Vec2 {
         self.cell
     }
+
+    /// Checks if items of the cells can be close enough to conflict, which is when the cells are neighbours.
+    pub fn is_near(&self, a: [i32; 2], b: [i32; 2]) -> bool {
+        (0..2).all(|n| {
+            let diff = (a[n] - b[n]).abs();
+            match self.poisson_type {
+                Type::Periodic => {
+                    let diff = diff % self.side[n] as i32;
+                    diff.min(self.side[n] as i32 - diff) <= self.reach[n]
+                }
+                Type::Normal => diff <= self.reach[n],
+            }
+        })
+    }
 }
 
-pub fn encode(v: &Vec2, side: usize, poisson_type: Type) -> Option<usize> {
+/// Returns the position of the cell in the grid, wrapping it around for `Type::Periodic`.
+pub fn encode(index: [i32; 2], side: [usize; 2], poisson_type: Type) -> Option<usize> {
     use crate::Type::*;
-    let mut index = 0;
+    let mut result = 0;
     for n in 0..2 {
-        let n = v[n];
+        let side_n = side[n] as i32;
         let cur = match poisson_type {
-            Periodic => (n as isize)
-                .modulo(side as isize) as usize,
+            Periodic => index[n].rem_euclid(side_n),
             Normal => {
-                if n < 0.0 || n >= side as f32 {
+                if index[n] < 0 || index[n] >= side_n {
                     return None;
                 }
-                n as usize
+                index[n]
             }
         };
-        index = (index + cur) * side;
+        result = result * side[n] + cur as usize;
     }
-    Some(index / side)
+    Some(result)
 }
 
-pub fn decode(index: usize, side: usize) -> Option<Vec2> {
-    if index >= side.pow(2) {
+pub fn decode(cell: usize, side: [usize; 2]) -> Option<[i32; 2]> {
+    if cell >= side[0] * side[1] {
         return None;
     }
-    let mut result = Vec2::zero();
-    let mut last = index;
+    let mut result = [0; 2];
+    let mut last = cell;
     for n in (0..2).rev() {
-        let cur = last / side;
-        result[n] = (last - cur * side) as f32;
-        last = cur;
+        result[n] = (last % side[n]) as i32;
+        last /= side[n];
     }
     Some(result)
 }
 
 #[test]
 fn encoding_decoding_works() {
-    let n = Vec2::new(10.0, 7.0);
+    let n = [10, 7];
     assert_eq!(
         n,
-        decode(encode(&n, 15, Type::Normal).unwrap(), 15).unwrap(),
+        decode(encode(n, [15, 15], Type::Normal).unwrap(), [15, 15]).unwrap()
     );
 }
 
 #[test]
 fn encoding_decoding_at_edge_works() {
-    let n = Vec2::new(14.0, 14.0);
+    let n = [14, 14];
+    assert_eq!(
+        n,
+        decode(encode(n, [15, 15], Type::Normal).unwrap(), [15, 15]).unwrap()
+    );
+}
+
+#[test]
+fn encoding_decoding_rectangle_works() {
+    let n = [3, 12];
+    assert_eq!(
+        n,
+        decode(encode(n, [4, 15], Type::Normal).unwrap(), [4, 15]).unwrap()
+    );
+    let n = [14, 2];
     assert_eq!(
         n,
-        decode(encode(&n, 15, Type::Normal).unwrap(), 15).unwrap()
+        decode(encode(n, [15, 4], Type::Normal).unwrap(), [15, 4]).unwrap()
+    );
+}
+
+#[test]
+fn encoding_periodic_wraps_around() {
+    let n = [-1, 4];
+    assert_eq!(
+        encode([3, 0], [4, 4], Type::Normal),
+        encode(n, [4, 4], Type::Periodic)
     );
 }
 
 #[test]
 fn encoding_outside_of_area_fails() {
-    let n = Vec2::new(9.0, 7.0);
-    assert_eq!(None, encode(&n, 9, Type::Normal));
-    let n = Vec2::new(7.0, 9.0);
-    assert_eq!(None, encode(&n, 9, Type::Normal));
+    assert_eq!(None, encode([9, 7], [9, 9], Type::Normal));
+    assert_eq!(None, encode([7, 9], [9, 9], Type::Normal));
+    assert_eq!(None, encode([7, 4], [9, 4], Type::Normal));
+    assert_eq!(None, encode([-1, 0], [9, 4], Type::Normal));
 }
 
 #[test]
 fn decoding_outside_of_area_fails() {
-    assert_eq!(None, decode(100, 10));
+    assert_eq!(None, decode(100, [10, 10]));
+    assert_eq!(None, decode(40, [10, 4]));
 }
 
-pub fn choose_random_sample<R>(rng: &mut R, grid: &Grid, index: Vec2, level: usize) -> Vec2
+pub fn choose_random_sample<R, T>(
+    rng: &mut R,
+    grid: &Grid<T>,
+    index: [i32; 2],
+    level: usize,
+) -> Vec2
 where
     R: Rng,
 {
-    let side = 2usize.pow(level as u32);
-    let spacing = grid.cell / (side as f32);
-    (index + rng.gen()) * spacing
+    let spacing = grid.cell / 2f32.powi(level as i32);
+    index_to_sample(index, spacing) + rng.gen::<Vec2>() * spacing
 }
 
 #[test]
 fn random_point_is_between_right_values_top_lvl() {
     use rand::{rngs::SmallRng, SeedableRng};
     let mut rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
-    let radius = 0.2;
-    let grid = Grid::new(radius, Type::Normal);
+    let grid: Grid = Grid::new(&Builder::with_radius(0.2, Type::Normal));
     for _ in 0..1000 {
-        let result = choose_random_sample(&mut rand, &grid, Vec2::zero(), 0);
+        let result = choose_random_sample(&mut rand, &grid, [0, 0], 0);
         assert!(result.x() >= 0.0);
-        assert!(result.x() < grid.cell);
+        assert!(result.x() < grid.cell.x());
         assert!(result.y() >= 0.0);
-        assert!(result.y() < grid.cell);
+        assert!(result.y() < grid.cell.y());
     }
 }
 
-pub fn sample_to_index(value: &Vec2, side: usize) -> Vec2 {
-    let mut cur = value.clone();
-    for n in 0..2 {
-        cur[n] = (cur[n] * (side as f32)).floor();
-    }
-    cur
+#[test]
+fn grid_covers_rectangle_exactly() {
+    let dimensions = Vec2::new(4.0, 1.0);
+    let grid: Grid = Grid::new(&Builder::with_domain(4.0, 1.0, 0.1, Type::Normal));
+    let side = grid.side();
+    assert_eq!(
+        dimensions,
+        Vec2::new(side[0] as f32, side[1] as f32) * grid.cell()
+    );
+    assert!(grid.cell().x() <= 0.1 * 2f32.sqrt());
+    assert!(grid.cell().y() <= 0.1 * 2f32.sqrt());
 }
 
-pub fn index_to_sample(value: &Vec2, side: usize) -> Vec2 {
-    let mut cur = value.clone();
-    for n in 0..2 {
-        cur[n] = cur[n] / (side as f32);
-    }
-    cur
+#[test]
+fn grid_keeps_items_sharing_a_cell() {
+    let mut grid: Grid<usize> = Grid::new(&Builder::with_domain(1.0, 1.0, 0.1, Type::Periodic));
+    assert_eq!(Some(true), grid.is_empty_at([2, 3]));
+    assert_eq!(Ok(()), grid.insert([2, 3], 0));
+    assert_eq!(Ok(()), grid.insert([2, 3], 1));
+    // Periodic grids wrap the cells around.
+    assert_eq!(Ok(()), grid.insert([-1, 0], 2));
+    assert_eq!(Some(false), grid.is_empty_at([2, 3]));
+    let mut near = grid.neighbours([3, 3]).cloned().collect::<Vec<_>>();
+    near.sort_unstable();
+    assert_eq!(vec![0, 1], near);
+    let mut near = grid.neighbours([0, 0]).cloned().collect::<Vec<_>>();
+    near.sort_unstable();
+    assert_eq!(vec![2], near);
+
+    let mut grid: Grid<usize> = Grid::new(&Builder::with_domain(1.0, 1.0, 0.1, Type::Normal));
+    assert_eq!(Err(3), grid.insert([-1, 0], 3));
+    assert_eq!(None, grid.is_empty_at([-1, 0]));
+}
+
+pub fn sample_to_index(value: &Vec2, cell: Vec2) -> [i32; 2] {
+    let index = (*value / cell).floor();
+    [index.x() as i32, index.y() as i32]
+}
+
+/// Returns the corner of the cell, calculated in double precision so that cells on deep levels stay exact.
+pub fn index_to_sample(index: [i32; 2], spacing: Vec2) -> Vec2 {
+    Vec2::new(
+        (index[0] as f64 * spacing.x() as f64) as f32,
+        (index[1] as f64 * spacing.y() as f64) as f32,
+    )
+}
+
+/// Returns the lower and upper corners of the cell.
+pub fn cell_bounds(index: [i32; 2], spacing: Vec2) -> (Vec2, Vec2) {
+    (
+        index_to_sample(index, spacing),
+        index_to_sample([index[0] + 1, index[1] + 1], spacing),
+    )
 }
 
 pub fn is_disk_free(
     grid: &Grid,
     poisson: &Builder,
-    index: Vec2,
+    index: [i32; 2],
     level: usize,
     sample: Vec2,
     outside: &[Vec2],
 ) -> bool {
     let parent = get_parent(index, level);
-    let sqradius = (2.0 * poisson.radius).powi(2);
-    // NOTE: This does unnecessary checks for corners, but it doesn't affect much in higher dimensions: 5^d vs 5^d - 2d
-    each_combination(&[-2.0, -1.0, 0.0, 1.0, 2.0])
-        .filter_map(|t| grid.get(parent.clone() + t))
-        .flat_map(|t| t)
-        .all(|v| sqdist(v.clone(), sample.clone(), poisson.poisson_type) >= sqradius)
-        && is_valid(poisson, outside, sample)
+    is_valid(poisson, grid.neighbours(parent), sample) && is_valid(poisson, outside, sample)
 }
 
-pub fn is_valid(poisson: &Builder, samples: &[Vec2], sample: Vec2) -> bool {
-    let sqradius = (2.0 * poisson.radius).powi(2);
-    samples
-        .iter()
-        .all(|t| sqdist(t.clone(), sample.clone(), poisson.poisson_type) >= sqradius)
+pub fn is_valid<'a, I>(poisson: &Builder, samples: I, sample: Vec2) -> bool
+where
+    I: IntoIterator<Item = &'a Vec2>,
+{
+    let radius = poisson.radius_at(sample.into());
+    samples.into_iter().all(|&t| {
+        let sqradius = (radius + poisson.radius_at(t.into())).powi(2);
+        sqdist(t, sample, poisson.dimensions, poisson.poisson_type) >= sqradius
+    })
 }
 
-pub fn sqdist(v1: Vec2, v2: Vec2, poisson_type: Type) -> f32 {
+pub fn is_class_disk_free(
+    grid: &Grid<(Vec2, usize)>,
+    poisson: &Builder,
+    classes: &Classes,
+    index: [i32; 2],
+    class: usize,
+    sample: Vec2,
+    outside: &[(Vec2, usize)],
+) -> bool {
+    grid.neighbours(index).chain(outside).all(|&(t, c)| {
+        let sqradius = (2.0 * classes.radius(class, c)).powi(2);
+        sqdist(t, sample, poisson.dimensions, poisson.poisson_type) >= sqradius
+    })
+}
+
+pub fn sqdist(v1: Vec2, v2: Vec2, dimensions: Vec2, poisson_type: Type) -> f32 {
     use crate::Type::*;
     let diff = v2 - v1;
     match poisson_type {
-        Periodic => each_combination(&[-1.0, 0.0, 1.0])
-            .map(|v| (diff.clone() + v).length_squared())
-            .fold(std::f32::MAX, |a, b| a.min(b)),
+        Periodic => each_combination(&[-1, 0, 1])
+            .map(|[x, y]| (diff + Vec2::new(x as f32, y as f32) * dimensions).length_squared())
+            .fold(f32::MAX, |a, b| a.min(b)),
         Normal => diff.length_squared(),
     }
 }
 
-pub fn get_parent(mut index: Vec2, level: usize) -> Vec2 {
-    let split = 2usize.pow(level as u32);
-    for n in 0..2 {
-        index[n] = (index[n] / (split as f32)).floor();
-    }
-    index
+#[test]
+fn periodic_distance_wraps_around_rectangle() {
+    let dimensions = Vec2::new(4.0, 1.0);
+    let v1 = Vec2::new(0.1, 0.5);
+    let v2 = Vec2::new(3.9, 0.5);
+    assert!((sqdist(v1, v2, dimensions, Type::Periodic) - 0.04).abs() < 1e-5);
+    assert!((sqdist(v1, v2, dimensions, Type::Normal) - 14.44).abs() < 1e-4);
+}
+
+pub fn get_parent(index: [i32; 2], level: usize) -> [i32; 2] {
+    [index[0] >> level, index[1] >> level]
 }
 
 #[test]
 fn getting_parent_works() {
     let divides = 4;
-    let cells_per_cell = 2usize.pow(divides as u32);
-    let testee = Vec2::new(1.0, 2.0);
+    let cells_per_cell = 2i32.pow(divides as u32);
+    let testee = [1, 2];
     assert_eq!(
         testee,
         get_parent(
-            (testee * cells_per_cell as f32) + Vec2::new(0.0, 15.0),
+            [testee[0] * cells_per_cell, testee[1] * cells_per_cell + 15],
             divides
         )
     );
+    assert_eq!([-1, 0], get_parent([-1, 15], divides));
 }
 
 pub struct CombiIter<'a> {
     cur: usize,
-    choices: &'a [f32],
+    choices: &'a [i32],
 }
 
 impl<'a> Iterator for CombiIter<'a> {
-    type Item = Vec2;
+    type Item = [i32; 2];
     fn next(&mut self) -> Option<Self::Item> {
         let len = self.choices.len();
         if self.cur >= len.pow(2) {
             None
         } else {
-            let mut result = Vec2::zero();
+            let mut result = [0; 2];
             let mut div = self.cur;
             self.cur += 1;
-            for n in 0..2 {
+            for r in &mut result {
                 let rem = div % len;
                 div /= len;
-                let choice = self.choices[rem as usize].clone();
-                result[n] = choice as f32;
+                *r = self.choices[rem];
             }
             Some(result)
         }
@@ -239,11 +447,8 @@ impl<'a> Iterator for CombiIter<'a> {
 }
 
 /// Iterates through all combinations of vectors with allowed values as scalars.
-pub fn each_combination(choices: &[f32]) -> CombiIter {
-    CombiIter {
-        cur: 0,
-        choices,
-    }
+pub fn each_combination(choices: &[i32]) -> CombiIter<'_> {
+    CombiIter { cur: 0, choices }
 }
 
 /// Trait that allows flat mapping inplace.
@@ -275,13 +480,44 @@ fn mapping_inplace_works() {
     let mut result = vec.clone();
     let func = |t| {
         match t % 3 {
-            0 => (0..0),
-            1 => (0..1),
-            _ => (0..2),
+            0 => 0..0,
+            1 => 0..1,
+            _ => 0..2,
         }
         .map(move |n| t + n)
     };
     result.flat_map_inplace(&func);
     let mut expected = vec.into_iter().flat_map(func).collect::<Vec<_>>();
-    assert_eq!(expected.sort(), result.sort());
+    expected.sort();
+    result.sort();
+    assert_eq!(expected, result);
+}
+
+/// Returns the phase of the block in a 2 × 2 colouring, so that blocks of the same phase never touch.
+pub fn phase(block: [i32; 2]) -> i32 {
+    block[0].rem_euclid(2) + 2 * block[1].rem_euclid(2)
+}
+
+/// Mixes the seed with the block coordinates with SplitMix64 steps.
+pub fn mix_seed(seed: u64, block: [i32; 2]) -> u64 {
+    let mut hash = seed;
+    for &c in &block {
+        hash = (hash ^ c as u32 as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^= hash >> 31;
+    }
+    hash
+}
+
+#[test]
+fn neighbouring_blocks_have_different_phases() {
+    for y in -3..3 {
+        for x in -3..3 {
+            assert_ne!(phase([x, y]), phase([x + 1, y]));
+            assert_ne!(phase([x, y]), phase([x, y + 1]));
+            assert_ne!(phase([x, y]), phase([x + 1, y + 1]));
+            assert_ne!(phase([x, y]), phase([x + 1, y - 1]));
+        }
+    }
 }
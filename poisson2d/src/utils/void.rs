@@ -0,0 +1,283 @@
+//! Convex polygons in double precision that cover the parts of cells no disk covers yet.
+//!
+//! Based on Ebeida, Mohamed S., et al. "Efficient maximal Poisson-disk sampling." ACM Transactions on Graphics (TOG)
+//! 30.4 (2011): 49.
+
+use rand::Rng;
+
+pub type Point = [f64; 2];
+
+/// Amount of chords each arc of a circle crossing a polygon is approximated with.
+const ARC_CHORDS: usize = 4;
+
+/// Returns the rectangle as a polygon with counter-clockwise vertices.
+pub fn rect(min: Point, max: Point) -> Vec<Point> {
+    vec![min, [max[0], min[1]], max, [min[0], max[1]]]
+}
+
+pub fn area(polygon: &[Point]) -> f64 {
+    let mut result = 0.0;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        result += a[0] * b[1] - a[1] * b[0];
+    }
+    result / 2.0
+}
+
+/// Returns the lower and upper corners of the bounding box of the polygon.
+pub fn bounds(polygon: &[Point]) -> (Point, Point) {
+    let mut min = [f64::INFINITY; 2];
+    let mut max = [f64::NEG_INFINITY; 2];
+    for p in polygon {
+        for n in 0..2 {
+            min[n] = min[n].min(p[n]);
+            max[n] = max[n].max(p[n]);
+        }
+    }
+    (min, max)
+}
+
+/// Returns the squared distance from the point to the bounding box of the polygon.
+pub fn sqdist_to_bounds(polygon: &[Point], point: Point) -> f64 {
+    let (min, max) = bounds(polygon);
+    (0..2)
+        .map(|n| (min[n] - point[n]).max(point[n] - max[n]).max(0.0).powi(2))
+        .sum()
+}
+
+fn cross(o: Point, a: Point, b: Point) -> f64 {
+    (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+}
+
+fn contains(polygon: &[Point], point: Point) -> bool {
+    (0..polygon.len()).all(|i| cross(polygon[i], polygon[(i + 1) % polygon.len()], point) >= 0.0)
+}
+
+/// Keeps the part of the polygon on the left side of the line going from a through b.
+fn clip(polygon: &[Point], a: Point, b: Point) -> Vec<Point> {
+    let mut result = vec![];
+    for (i, &p) in polygon.iter().enumerate() {
+        let q = polygon[(i + 1) % polygon.len()];
+        let (side_p, side_q) = (cross(a, b, p), cross(a, b, q));
+        if side_p >= 0.0 {
+            result.push(p);
+        }
+        if (side_p >= 0.0) != (side_q >= 0.0) {
+            let t = side_p / (side_p - side_q);
+            result.push([p[0] + t * (q[0] - p[0]), p[1] + t * (q[1] - p[1])]);
+        }
+    }
+    result
+}
+
+/// Returns the convex hull of the points with counter-clockwise vertices.
+fn convex_hull(mut points: Vec<Point>) -> Vec<Point> {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut hull: Vec<Point> = vec![];
+    for pass in 0..2 {
+        let start = hull.len();
+        for &p in points.iter() {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+        if pass == 0 {
+            points.reverse();
+        }
+    }
+    hull
+}
+
+/// Returns convex polygons which together cover the part of the polygon outside of the disk.
+///
+/// The part inside of the disk is approximated from inside by a convex polygon with vertices on the circle, so the
+/// polygons returned can cover thin slivers of the disk too.
+pub fn subtract(polygon: &[Point], center: Point, radius: f64) -> Vec<Vec<Point>> {
+    let sqradius = radius * radius;
+    let inside = |p: &Point| (p[0] - center[0]).powi(2) + (p[1] - center[1]).powi(2) <= sqradius;
+    if polygon.iter().all(inside) {
+        return vec![];
+    }
+    let on_circle = |angle: f64| {
+        [
+            center[0] + radius * angle.cos(),
+            center[1] + radius * angle.sin(),
+        ]
+    };
+    let mut points = polygon.iter().cloned().filter(inside).collect::<Vec<_>>();
+    let mut angles = vec![];
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let d = [b[0] - a[0], b[1] - a[1]];
+        let f = [a[0] - center[0], a[1] - center[1]];
+        let qa = d[0] * d[0] + d[1] * d[1];
+        let qb = 2.0 * (f[0] * d[0] + f[1] * d[1]);
+        let qc = f[0] * f[0] + f[1] * f[1] - sqradius;
+        let discriminant = qb * qb - 4.0 * qa * qc;
+        if qa == 0.0 || discriminant <= 0.0 {
+            continue;
+        }
+        for &t in &[
+            (-qb - discriminant.sqrt()) / (2.0 * qa),
+            (-qb + discriminant.sqrt()) / (2.0 * qa),
+        ] {
+            if (0.0..=1.0).contains(&t) {
+                let p = [a[0] + t * d[0], a[1] + t * d[1]];
+                points.push(p);
+                angles.push((p[1] - center[1]).atan2(p[0] - center[0]));
+            }
+        }
+    }
+    if angles.is_empty() {
+        // Without crossings the disk either misses the polygon or lies completely inside of it.
+        if !contains(polygon, on_circle(0.0)) {
+            return vec![polygon.to_vec()];
+        }
+        let steps = 4 * ARC_CHORDS;
+        points.extend(
+            (0..steps).map(|i| on_circle(i as f64 * 2.0 * std::f64::consts::PI / steps as f64)),
+        );
+    } else {
+        angles.sort_by(f64::total_cmp);
+        for i in 0..angles.len() {
+            let low = angles[i];
+            let high = angles
+                .get(i + 1)
+                .cloned()
+                .unwrap_or(angles[0] + 2.0 * std::f64::consts::PI);
+            // Arcs between neighbouring crossings are either completely inside or outside of a convex polygon.
+            if contains(polygon, on_circle((low + high) / 2.0)) {
+                points.extend(
+                    (1..ARC_CHORDS)
+                        .map(|j| on_circle(low + (high - low) * j as f64 / ARC_CHORDS as f64)),
+                );
+            }
+        }
+    }
+    let hull = convex_hull(points);
+    if hull.len() < 3 {
+        return vec![polygon.to_vec()];
+    }
+    // Pieces of the polygon outside of each edge of the hull and inside of the edges before it.
+    let mut rest = polygon.to_vec();
+    let mut result = vec![];
+    for i in 0..hull.len() {
+        let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+        let outside = clip(&rest, b, a);
+        // Edges of the hull along the edges of the polygon leave nothing outside of them.
+        if area(&outside) > 0.0 {
+            result.push(outside);
+        }
+        rest = clip(&rest, a, b);
+        if rest.len() < 3 {
+            break;
+        }
+    }
+    result
+}
+
+/// Cuts the polygon into halves across the longer side of its bounding box.
+pub fn split(polygon: &[Point]) -> [Vec<Point>; 2] {
+    let (min, max) = bounds(polygon);
+    let n = if max[0] - min[0] >= max[1] - min[1] {
+        0
+    } else {
+        1
+    };
+    let mut a = [0.0; 2];
+    a[n] = (min[n] + max[n]) / 2.0;
+    let mut b = a;
+    b[1 - n] = 1.0;
+    [clip(polygon, a, b), clip(polygon, b, a)]
+}
+
+/// Chooses random point of the convex polygon with all points being equally likely.
+pub fn random_point<R>(rng: &mut R, polygon: &[Point]) -> Point
+where
+    R: Rng,
+{
+    let o = polygon[0];
+    let triangles = (1..polygon.len() - 1)
+        .map(|i| {
+            (
+                polygon[i],
+                polygon[i + 1],
+                cross(o, polygon[i], polygon[i + 1]).max(0.0),
+            )
+        })
+        .collect::<Vec<_>>();
+    let total = triangles.iter().map(|t| t.2).sum::<f64>();
+    let mut choice = rng.gen::<f64>() * total;
+    let mut chosen = triangles[triangles.len() - 1];
+    for &t in &triangles {
+        if choice < t.2 {
+            chosen = t;
+            break;
+        }
+        choice -= t.2;
+    }
+    let (a, b, _) = chosen;
+    let (mut u, mut v) = (rng.gen::<f64>(), rng.gen::<f64>());
+    if u + v > 1.0 {
+        u = 1.0 - u;
+        v = 1.0 - v;
+    }
+    [
+        o[0] + u * (a[0] - o[0]) + v * (b[0] - o[0]),
+        o[1] + u * (a[1] - o[1]) + v * (b[1] - o[1]),
+    ]
+}
+
+#[test]
+fn subtracting_disk_keeps_uncovered_area() {
+    let square = rect([0.0, 0.0], [1.0, 1.0]);
+    let (center, radius) = ([0.0, 0.0], 0.8);
+    let pieces = subtract(&square, center, radius);
+    let covered = std::f64::consts::PI * radius * radius / 4.0;
+    let total = pieces.iter().map(|p| area(p)).sum::<f64>();
+    assert!(total >= 1.0 - covered - 1e-9);
+    assert!(total < 1.0 - covered + 0.05);
+    for piece in &pieces {
+        assert!(area(piece) > 0.0);
+        assert!(piece.iter().all(|p| (-1e-12..=1.0 + 1e-12).contains(&p[0])));
+    }
+    assert!(subtract(&square, [0.5, 0.5], 0.8).is_empty());
+    assert_eq!(vec![square.clone()], subtract(&square, [3.0, 0.5], 0.8));
+}
+
+#[test]
+fn subtracting_disk_inside_polygon_keeps_ring() {
+    let square = rect([0.0, 0.0], [1.0, 1.0]);
+    let pieces = subtract(&square, [0.5, 0.5], 0.2);
+    let total = pieces.iter().map(|p| area(p)).sum::<f64>();
+    assert!(total >= 1.0 - std::f64::consts::PI * 0.04 - 1e-9);
+    assert!(total < 1.0 - 0.1);
+}
+
+#[test]
+fn splitting_keeps_area() {
+    let polygon = rect([0.0, 0.0], [2.0, 1.0]);
+    for half in &split(&polygon) {
+        assert!((area(half) - 1.0).abs() < 1e-12);
+        let (min, max) = bounds(half);
+        assert!(max[0] <= 1.0 || min[0] >= 1.0);
+    }
+}
+
+#[test]
+fn random_points_stay_in_polygon() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let mut rng = SmallRng::seed_from_u64(42);
+    let polygon = vec![[0.0, 0.0], [2.0, 0.0], [3.0, 1.0], [1.0, 2.0]];
+    for _ in 0..1000 {
+        assert!(contains(&polygon, random_point(&mut rng, &polygon)));
+    }
+}
@@ -0,0 +1,249 @@
+//! Module that contains checks for whether samples form a valid and maximal Poisson disk sampling.
+//!
+//! The checks take the samples as they are, so they can be used for samplings loaded from elsewhere or edited by hand.
+//! The builder describes the radius, area and type the samples should satisfy.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::sampling::Sampling;
+use crate::utils::sqdist;
+use crate::{Builder, Type};
+
+/// Pair of samples which are closer to each other than their radii allow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Violation {
+    /// Index of the first sample of the pair.
+    pub first: usize,
+    /// Index of the second sample of the pair, which is larger than the first one.
+    pub second: usize,
+    /// Distance between the samples.
+    pub distance: f32,
+    /// Smallest distance allowed between the samples, which is the sum of their radii.
+    pub required: f32,
+}
+
+/// Region where another sample would fit without coming too close to the existing ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gap {
+    /// Point of the region farthest from the samples.
+    pub position: mint::Vector2<f32>,
+    /// Distance from the position to the closest sample.
+    pub distance: f32,
+}
+
+/// Returns all pairs of samples that are closer to each other than twice the radius of the builder,
+/// or than the sum of their radii if the builder has a radius field.
+///
+/// # Panics
+///
+/// Panics if the radius of the builder isn't in (0, √(width² + height²) / 2].
+pub fn violations(samples: &[mint::Vector2<f32>], poisson: &Builder) -> Vec<Violation> {
+    let sampling = Sampling::new(poisson, samples.iter().cloned());
+    let mut result = vec![];
+    for (first, &sample) in samples.iter().enumerate() {
+        let mut near = sampling.within(sample, 2.0 * poisson.max_radius());
+        near.sort_unstable();
+        for second in near.into_iter().filter(|&j| j > first) {
+            let distance = sqdist(
+                sample.into(),
+                samples[second].into(),
+                poisson.dimensions,
+                poisson.poisson_type,
+            )
+            .sqrt();
+            let required = poisson.radius_at(sample) + poisson.radius_at(samples[second]);
+            if distance < required {
+                result.push(Violation {
+                    first,
+                    second,
+                    distance,
+                    required,
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Returns the regions of the sampled area where another sample would fit, ordered from the largest distance to the
+/// samples to the smallest. The sampling is maximal if there are none.
+///
+/// Each connected region is reported once, at its point farthest from the samples.
+/// If there are no samples the whole area is one region with infinite distance.
+/// For builders with a polygon or predicate only the regions whose farthest point is inside the polygon and accepted
+/// by the predicate are reported.
+/// Radius fields aren't supported.
+///
+/// # Panics
+///
+/// Panics if the builder has a radius field or its radius isn't in (0, √(width² + height²) / 2].
+pub fn gaps(samples: &[mint::Vector2<f32>], poisson: &Builder) -> Vec<Gap> {
+    assert!(
+        poisson.radius_field.is_none(),
+        "Radius fields aren't supported."
+    );
+    if samples.is_empty() {
+        return vec![Gap {
+            position: (poisson.dimensions / 2.0).into(),
+            distance: f32::INFINITY,
+        }];
+    }
+    let limit = 2.0 * poisson.radius;
+    let cells = Sampling::new(poisson, samples.iter().cloned())
+        .triangulate()
+        .voronoi();
+
+    // Distance to the samples only has local maxima at the corners of Voronoi cells, so every region contains one.
+    // Regions inside a cell always reach its boundary, so the corners connected by free edges form the regions.
+    let mut corners = Corners::new(poisson);
+    let mut parents = vec![];
+    for (&sample, cell) in samples.iter().zip(&cells) {
+        let sample: Vec2 = sample.into();
+        let ids = cell
+            .iter()
+            .map(|&v| {
+                let v: Vec2 = v.into();
+                let distance = (v - sample).length();
+                if distance > limit {
+                    let id = corners.insert(v, distance);
+                    if id == parents.len() {
+                        parents.push(id);
+                    }
+                    Some(id)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        for k in 0..cell.len() {
+            let next = (k + 1) % cell.len();
+            if let (Some(a), Some(b)) = (ids[k], ids[next]) {
+                if segment_distance(sample, cell[k].into(), cell[next].into()) > limit {
+                    let (a, b) = (find(&mut parents, a), find(&mut parents, b));
+                    parents[a] = b;
+                }
+            }
+        }
+    }
+
+    let mut regions: HashMap<usize, usize> = HashMap::new();
+    for id in 0..parents.len() {
+        let root = find(&mut parents, id);
+        let best = regions.entry(root).or_insert(id);
+        if corners.distances[id] > corners.distances[*best] {
+            *best = id;
+        }
+    }
+    let mut result = regions
+        .values()
+        .map(|&id| Gap {
+            position: corners.positions[id].into(),
+            distance: corners.distances[id],
+        })
+        .filter(|gap| {
+            let position = gap.position.into();
+            poisson.accepts(position)
+        })
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+    result
+}
+
+/// Smallest distance from the point to the line segment.
+fn segment_distance(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let edge = b - a;
+    let t = if edge.length_squared() > 0.0 {
+        ((point - a).dot(edge) / edge.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + edge * t - point).length()
+}
+
+fn find(parents: &mut [usize], mut id: usize) -> usize {
+    while parents[id] != id {
+        parents[id] = parents[parents[id]];
+        id = parents[id];
+    }
+    id
+}
+
+/// Corners of Voronoi cells, where the corners neighbouring cells share are merged together.
+struct Corners {
+    dimensions: Vec2,
+    poisson_type: Type,
+    spacing: f32,
+    buckets: HashMap<(i64, i64), Vec<usize>>,
+    positions: Vec<Vec2>,
+    distances: Vec<f32>,
+}
+
+impl Corners {
+    fn new(poisson: &Builder) -> Self {
+        Corners {
+            dimensions: poisson.dimensions,
+            poisson_type: poisson.poisson_type,
+            spacing: 1e-3 * poisson.radius,
+            buckets: HashMap::new(),
+            positions: vec![],
+            distances: vec![],
+        }
+    }
+
+    /// Returns the id of the corner at the position, adding it if it isn't there yet.
+    fn insert(&mut self, mut position: Vec2, distance: f32) -> usize {
+        if let Type::Periodic = self.poisson_type {
+            for n in 0..2 {
+                position[n] = position[n].rem_euclid(self.dimensions[n]);
+                // Tiny negative values round up to the dimension itself.
+                if position[n] >= self.dimensions[n] {
+                    position[n] = 0.0;
+                }
+            }
+        }
+        let (x, y) = self.bucket(position);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let bucket = self.wrap((x + dx, y + dy));
+                for &id in self.buckets.get(&bucket).into_iter().flatten() {
+                    let d = sqdist(
+                        self.positions[id],
+                        position,
+                        self.dimensions,
+                        self.poisson_type,
+                    );
+                    if d <= self.spacing * self.spacing {
+                        return id;
+                    }
+                }
+            }
+        }
+        let id = self.positions.len();
+        self.positions.push(position);
+        self.distances.push(distance);
+        self.buckets.entry((x, y)).or_default().push(id);
+        id
+    }
+
+    fn bucket(&self, position: Vec2) -> (i64, i64) {
+        let x = (position.x() / self.spacing).floor() as i64;
+        let y = (position.y() / self.spacing).floor() as i64;
+        self.wrap((x, y))
+    }
+
+    /// Buckets at the opposite edges of a periodic area are next to each other.
+    fn wrap(&self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self.poisson_type {
+            Type::Normal => (x, y),
+            Type::Periodic => {
+                let count = (self.dimensions / self.spacing).ceil();
+                (
+                    x.rem_euclid(count.x() as i64),
+                    y.rem_euclid(count.y() as i64),
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use poisson2d::analysis::{nearest_neighbours, pair_statistics, periodogram, RadialSpectrum};
+use poisson2d::sampling::Sampling;
+use poisson2d::Type::*;
+use poisson2d::{algorithm, Builder};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+fn ebeida(builder: &Builder, seeds: u8) -> Vec<Sampling> {
+    (0..seeds)
+        .map(|seed| {
+            builder
+                .clone()
+                .build(SmallRng::from_seed([seed; 16]), algorithm::Ebeida)
+                .generate()
+        })
+        .collect()
+}
+
+fn white_noise(builder: &Builder, samples: usize, seeds: u8) -> Vec<Sampling> {
+    let dimensions = builder.dimensions();
+    (0..seeds)
+        .map(|seed| {
+            let mut rng = SmallRng::from_seed([seed; 16]);
+            let points = (0..samples)
+                .map(|_| {
+                    [
+                        rng.gen_range(0.0, dimensions.x),
+                        rng.gen_range(0.0, dimensions.y),
+                    ]
+                    .into()
+                })
+                .collect::<Vec<_>>();
+            Sampling::new(builder, points)
+        })
+        .collect()
+}
+
+/// Averages the power over the rings with frequencies in the range.
+fn average(radial: &RadialSpectrum, min: f32, max: f32) -> f32 {
+    let powers = radial
+        .frequencies()
+        .into_iter()
+        .zip(radial.power())
+        .filter(|&(f, _)| min <= f && f < max)
+        .map(|(_, &p)| p)
+        .collect::<Vec<_>>();
+    assert!(!powers.is_empty());
+    powers.iter().sum::<f32>() / powers.len() as f32
+}
+
+/// Checks for blue noise profile: low power at low frequencies, peak around one over the minimum distance and
+/// flat power at high frequencies.
+fn assert_blue_noise(radial: &RadialSpectrum, radius: f32, low: f32) {
+    let peak = 1.0 / (2.0 * radius);
+    let frequencies = radial.frequencies();
+    assert!(frequencies.last().unwrap() > &(3.0 * peak));
+    let (i, &highest) = radial
+        .power()
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| frequencies[i] >= low)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .unwrap();
+    let low = average(radial, low, 0.5 * peak);
+    assert!(low < 0.2, "Power at low frequencies is {}.", low);
+    assert!(highest > 1.2, "Peak power is {}.", highest);
+    assert!(
+        0.7 * peak < frequencies[i] && frequencies[i] < 1.5 * peak,
+        "Peak is at {} instead of near {}.",
+        frequencies[i],
+        peak
+    );
+    let high = average(radial, 2.0 * peak, 3.0 * peak);
+    assert!(
+        0.8 < high && high < 1.2,
+        "Power at high frequencies is {}.",
+        high
+    );
+}
+
+#[test]
+fn test_ebeida_is_blue_noise_periodic() {
+    let builder = Builder::with_radius(0.04, Periodic);
+    let spectrum = periodogram(&ebeida(&builder, 10), 128);
+    assert_eq!(128, spectrum.size());
+    assert_blue_noise(&spectrum.radial(), builder.radius(), 1.0);
+}
+
+#[test]
+fn test_ebeida_is_blue_noise_normal() {
+    let builder = Builder::with_radius(0.04, Normal);
+    let spectrum = periodogram(&ebeida(&builder, 10), 128);
+    // Window blurs the lowest frequencies.
+    assert_blue_noise(&spectrum.radial(), builder.radius(), 4.0);
+}
+
+#[test]
+fn test_ebeida_is_blue_noise_rectangle() {
+    let builder = Builder::with_domain(2.0, 1.0, 0.04, Periodic);
+    let spectrum = periodogram(&ebeida(&builder, 5), 256);
+    assert_eq!(0.5, spectrum.frequency(1, 0).x);
+    assert_eq!(1.0, spectrum.frequency(0, 1).y);
+    assert_blue_noise(&spectrum.radial(), builder.radius(), 1.0);
+}
+
+#[test]
+fn test_white_noise_is_flat() {
+    for &ptype in &[Periodic, Normal] {
+        let builder = Builder::with_radius(0.02, ptype);
+        let samplings = white_noise(&builder, 1000, 10);
+        let radial = periodogram(&samplings, 64).radial();
+        let flat = average(&radial, 4.0, 32.0);
+        assert!(
+            0.9 < flat && flat < 1.1,
+            "Power of white noise is {}.",
+            flat
+        );
+        let anisotropy =
+            radial.anisotropy()[4..].iter().sum::<f32>() / (radial.anisotropy().len() - 4) as f32;
+        assert!(
+            0.05 < anisotropy && anisotropy < 0.15,
+            "Anisotropy of white noise is {}.",
+            anisotropy
+        );
+    }
+}
+
+#[test]
+fn test_periodogram_dc_is_sample_count() {
+    let builder = Builder::with_radius(0.05, Periodic);
+    let samplings = ebeida(&builder, 1);
+    let spectrum = periodogram(&samplings, 16);
+    assert!(
+        (spectrum.power(0, 0) - samplings[0].len() as f32).abs() < 1e-2 * samplings[0].len() as f32
+    );
+    // Power of real samples is symmetric.
+    assert!((spectrum.power(3, -5) - spectrum.power(-3, 5)).abs() < 1e-3);
+}
+
+#[test]
+#[should_panic]
+fn test_periodogram_needs_same_dimensions() {
+    let mut samplings = ebeida(&Builder::with_radius(0.05, Periodic), 1);
+    samplings.extend(ebeida(&Builder::with_domain(2.0, 1.0, 0.05, Periodic), 1));
+    let _ = periodogram(&samplings, 16);
+}
+
+#[test]
+fn test_white_noise_pair_statistics() {
+    for &ptype in &[Periodic, Normal] {
+        let builder = Builder::with_radius(0.02, ptype);
+        let samplings = white_noise(&builder, 1000, 10);
+        let stats = pair_statistics(&samplings, 0.2, 20);
+        assert_eq!(20, stats.distances().len());
+        assert!((stats.distances()[19] - 0.2).abs() < 1e-6);
+        let g = stats.pair_correlation();
+        let average = g[5..].iter().sum::<f32>() / (g.len() - 5) as f32;
+        assert!(
+            (average - 1.0).abs() < 0.05,
+            "Pair correlation of white noise is {}.",
+            average
+        );
+        for (&r, &l) in stats.distances().iter().zip(&stats.ripley_l()).skip(5) {
+            assert!(
+                (l - r).abs() < 0.05 * r,
+                "L({}) of white noise is {}.",
+                r,
+                l
+            );
+        }
+        let nearest = nearest_neighbours(&samplings);
+        assert_eq!(10000, nearest.distances().len());
+        let ratio = nearest.clark_evans().unwrap();
+        let expected = if let Periodic = ptype { 1.0 } else { 1.05 };
+        assert!(
+            (ratio - expected).abs() < 0.05,
+            "Clark-Evans ratio of white noise is {}.",
+            ratio
+        );
+    }
+}
+
+#[test]
+fn test_ebeida_pair_statistics() {
+    for &ptype in &[Periodic, Normal] {
+        let radius = 0.02;
+        let builder = Builder::with_domain(2.0, 1.0, radius, ptype);
+        let samplings = ebeida(&builder, 3);
+        let stats = pair_statistics(&samplings, 10.0 * radius, 40);
+        let g = stats.pair_correlation();
+        for (&r, &g) in stats.distances().iter().zip(&g) {
+            if r <= 2.0 * radius {
+                assert_eq!(0.0, g, "Samples are closer than {}.", r);
+            }
+        }
+        let (i, &peak) = g
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        assert!(peak > 1.5, "Peak of pair correlation is {}.", peak);
+        let at = stats.distances()[i];
+        assert!(
+            2.0 * radius < at && at < 3.0 * radius,
+            "Peak of pair correlation is at {}.",
+            at
+        );
+        let tail = g[30..].iter().sum::<f32>() / 10.0;
+        assert!(
+            (tail - 1.0).abs() < 0.1,
+            "Pair correlation at far distances is {}.",
+            tail
+        );
+
+        let nearest = nearest_neighbours(&samplings);
+        assert!(nearest.min() >= 2.0 * radius);
+        assert!(nearest.mean().unwrap() < 3.0 * radius);
+        assert!(nearest.standard_deviation().unwrap() < radius);
+        let clark_evans = nearest.clark_evans().unwrap();
+        assert!(clark_evans > 1.5, "Clark-Evans ratio is {}.", clark_evans);
+        let histogram = nearest.histogram(4.0 * radius, 8);
+        assert_eq!(nearest.distances().len(), histogram.iter().sum::<usize>());
+        assert_eq!(0, histogram[..4].iter().sum::<usize>());
+    }
+}
+
+#[test]
+fn test_nearest_neighbours_without_neighbours() {
+    let builder = Builder::with_radius(0.1, Normal);
+    let empty = Sampling::new(&builder, vec![]);
+    let single = Sampling::new(&builder, vec![[0.5, 0.5].into()]);
+    for sampling in &[empty, single] {
+        let nearest = nearest_neighbours(Some(sampling));
+        assert!(nearest.distances().is_empty());
+        assert_eq!(None, nearest.mean());
+        assert_eq!(None, nearest.standard_deviation());
+        assert_eq!(None, nearest.clark_evans());
+    }
+}
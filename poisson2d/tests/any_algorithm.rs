@@ -0,0 +1,63 @@
+use poisson2d::algorithm::{self, AnyAlgorithm, Creator};
+use poisson2d::{Builder, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn check_same<A>(builder: &Builder, algo: A)
+where
+    A: Creator + Into<AnyAlgorithm>,
+{
+    for seed in 0..3 {
+        let rng = SmallRng::seed_from_u64(seed);
+        let expected = builder.clone().build(rng.clone(), algo).generate().to_vec();
+        let result = builder.clone().build(rng, algo.into()).generate().to_vec();
+        assert_eq!(
+            expected, result,
+            "Choosing '{:?}' while running changed the samples.",
+            algo
+        );
+    }
+}
+
+#[test]
+fn any_algorithm_generates_same_samples() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_samples(300, 0.8, ptype);
+        check_same(&builder, algorithm::Ebeida);
+        check_same(
+            &builder,
+            algorithm::EbeidaConfig::new()
+                .with_exact(true)
+                .with_throw_factor(0.5),
+        );
+        check_same(&builder, algorithm::Bridson);
+        check_same(
+            &builder,
+            algorithm::BridsonConfig::new().with_candidates(algorithm::Candidates::Roberts),
+        );
+        check_same(
+            &builder,
+            algorithm::BridsonConfig::new().with_fill_gaps(true),
+        );
+        check_same(&builder, algorithm::SampleElimination);
+    }
+}
+
+#[test]
+fn any_algorithm_grows_from_seeds() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let seed = [0.5, 0.5].into();
+    let mut expected = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(0), algorithm::Bridson)
+        .into_iter();
+    let mut result = builder
+        .build(
+            SmallRng::seed_from_u64(0),
+            AnyAlgorithm::from(algorithm::Bridson),
+        )
+        .into_iter();
+    expected.seed(seed);
+    result.seed(seed);
+    assert_eq!(expected.size_hint(), result.size_hint());
+    assert_eq!(expected.collect::<Vec<_>>(), result.collect::<Vec<_>>());
+}
@@ -0,0 +1,236 @@
+use poisson2d::algorithm::{BridsonConfig, Candidates};
+use poisson2d::{validity, Builder, Error, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+const STRATEGIES: [Candidates; 3] = [Candidates::Radial, Candidates::Uniform, Candidates::Roberts];
+
+fn generate(builder: &Builder, config: BridsonConfig, seed: u64) -> Vec<mint::Vector2<f32>> {
+    builder
+        .clone()
+        .build(SmallRng::seed_from_u64(seed), config)
+        .generate()
+        .to_vec()
+}
+
+fn average_count(builder: &Builder, config: BridsonConfig) -> f32 {
+    (0..5)
+        .map(|seed| generate(builder, config, seed).len())
+        .sum::<usize>() as f32
+        / 5.0
+}
+
+#[test]
+fn every_strategy_keeps_samples_apart() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builders = vec![
+            Builder::with_domain(2.0, 1.0, 0.03, ptype),
+            Builder::with_radius(0.01, ptype).with_radius_field(0.03, |p| 0.01 + 0.02 * p.x),
+        ];
+        for builder in &builders {
+            for &candidates in &STRATEGIES {
+                for &count in &[1, 7, 30] {
+                    let config = BridsonConfig::new()
+                        .with_candidates(candidates)
+                        .with_candidate_count(count);
+                    for seed in 0..2 {
+                        let samples = generate(builder, config, seed);
+                        assert!(!samples.is_empty());
+                        assert!(
+                            validity::violations(&samples, builder).is_empty(),
+                            "{:?}",
+                            config
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn more_candidates_pack_more_samples() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    for &candidates in &STRATEGIES {
+        let few = average_count(
+            &builder,
+            BridsonConfig::new()
+                .with_candidates(candidates)
+                .with_candidate_count(3),
+        );
+        let many = average_count(
+            &builder,
+            BridsonConfig::new()
+                .with_candidates(candidates)
+                .with_candidate_count(30),
+        );
+        assert!(few < many, "{:?}: {} < {}", candidates, few, many);
+    }
+}
+
+#[test]
+fn roberts_candidates_pack_densest() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let count =
+        |candidates| average_count(&builder, BridsonConfig::new().with_candidates(candidates));
+    let (radial, uniform, roberts) = (
+        count(Candidates::Radial),
+        count(Candidates::Uniform),
+        count(Candidates::Roberts),
+    );
+    assert!(uniform < radial, "{} < {}", uniform, radial);
+    assert!(radial < roberts, "{} < {}", radial, roberts);
+}
+
+#[test]
+#[should_panic]
+fn zero_candidates() {
+    let _ = BridsonConfig::new().with_candidate_count(0);
+}
+
+#[test]
+fn try_candidate_count() {
+    assert_eq!(
+        Err(Error::ZeroCandidates),
+        BridsonConfig::new().try_with_candidate_count(0)
+    );
+    assert_eq!(
+        Ok(BridsonConfig::new().with_candidate_count(5)),
+        BridsonConfig::new().try_with_candidate_count(5)
+    );
+}
+
+#[test]
+fn sampling_grows_from_seeds() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let seeds: Vec<mint::Vector2<f32>> = vec![[0.5, 0.5].into(), [0.1, 0.9].into()];
+    for seed in 0..3 {
+        let mut iter = builder
+            .clone()
+            .build(SmallRng::seed_from_u64(seed), BridsonConfig::new())
+            .into_iter();
+        for &s in &seeds {
+            iter.seed(s);
+        }
+        let samples = iter.collect::<Vec<_>>();
+        let near_seed = |sample: mint::Vector2<f32>| {
+            seeds
+                .iter()
+                .any(|s| ((sample.x - s.x).powi(2) + (sample.y - s.y).powi(2)).sqrt() <= 4.0 * 0.02)
+        };
+        assert!(near_seed(samples[0]));
+        assert!(!samples.contains(&seeds[0]));
+        let mut all = samples.clone();
+        all.extend(&seeds);
+        assert!(validity::violations(&all, &builder).is_empty());
+    }
+}
+
+#[test]
+fn every_sample_is_reachable_from_seeds() {
+    let radius = 0.02;
+    // Strip in the middle is too wide for the sampling to grow across it.
+    let builder =
+        Builder::with_radius(radius, Type::Normal).with_predicate(|v| (v.x - 0.5).abs() > 0.1);
+    for seed in 0..3 {
+        let mut iter = builder
+            .clone()
+            .build(SmallRng::seed_from_u64(seed), BridsonConfig::new())
+            .into_iter();
+        iter.seed([0.2, 0.5].into());
+        let mut reached = vec![mint::Vector2::from([0.2, 0.5])];
+        let mut remaining = iter.collect::<Vec<_>>();
+        assert!(!remaining.is_empty());
+        while let Some(i) = remaining.iter().position(|s| {
+            reached
+                .iter()
+                .any(|r| ((s.x - r.x).powi(2) + (s.y - r.y).powi(2)).sqrt() <= 4.0 * radius)
+        }) {
+            reached.push(remaining.swap_remove(i));
+        }
+        assert!(
+            remaining.is_empty(),
+            "{} samples weren't reachable from the seed.",
+            remaining.len()
+        );
+    }
+}
+
+#[test]
+fn sampling_grows_from_seed_outside_of_area() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let mut iter = builder
+        .build(SmallRng::seed_from_u64(0), BridsonConfig::new())
+        .into_iter();
+    iter.seed([-0.03, 0.5].into());
+    let first = iter.next().unwrap();
+    assert!(first.x < 0.06 && (first.y - 0.5).abs() < 0.08);
+}
+
+#[test]
+fn filled_gaps_make_sampling_maximal() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builders = vec![
+            (Builder::with_domain(2.0, 1.0, 0.03, ptype), true),
+            // Gaps can't be found with radius fields.
+            (
+                Builder::with_radius(0.01, ptype).with_radius_field(0.03, |p| 0.01 + 0.02 * p.x),
+                false,
+            ),
+        ];
+        for (builder, find_gaps) in &builders {
+            for seed in 0..2 {
+                let samples = generate(builder, BridsonConfig::new().with_fill_gaps(true), seed);
+                assert!(samples.len() > generate(builder, BridsonConfig::new(), seed).len());
+                assert!(validity::violations(&samples, builder).is_empty());
+                if *find_gaps {
+                    for gap in validity::gaps(&samples, builder) {
+                        assert!(gap.distance < 2.0001 * builder.radius(), "{:?}", gap);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn size_hint_covers_filled_gaps() {
+    let builder = Builder::with_radius(0.02, Type::Periodic);
+    let mut iter = builder
+        .build(
+            SmallRng::seed_from_u64(0),
+            BridsonConfig::new().with_fill_gaps(true),
+        )
+        .into_iter();
+    let mut hints = vec![iter.size_hint()];
+    while iter.next().is_some() {
+        hints.push(iter.size_hint());
+    }
+    let count = hints.len() - 1;
+    for (n, &(lower, upper)) in hints.iter().enumerate() {
+        let remaining = count - n;
+        assert!(lower <= remaining, "{}: {} <= {}", n, lower, remaining);
+        assert!(upper.unwrap() >= remaining, "{:?} >= {}", upper, remaining);
+    }
+}
+
+#[test]
+fn filled_gaps_keep_restricting_samples() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let mut iter = builder
+        .clone()
+        .build(
+            SmallRng::seed_from_u64(0),
+            BridsonConfig::new().with_fill_gaps(true),
+        )
+        .into_iter();
+    let samples = iter.by_ref().collect::<Vec<_>>();
+    for &sample in &samples {
+        assert!(!iter.stays_legal(sample));
+    }
+    // Sample far outside of the area is out of reach of every other one.
+    let far = mint::Vector2::from([3.0, 3.0]);
+    assert!(iter.stays_legal(far));
+    iter.restrict(far);
+    assert!(!iter.stays_legal([3.01, 3.0].into()));
+    assert_eq!(None, iter.next());
+}
@@ -0,0 +1,136 @@
+use poisson2d::algorithm::{self, Creator};
+use poisson2d::validity;
+use poisson2d::{Builder, Type};
+use rand::rngs::SmallRng;
+
+fn chunks<A: Creator>(
+    poisson: &Builder,
+    seed: u64,
+    algo: A,
+    order: &[(i32, i32)],
+) -> Vec<Vec<mint::Vector2<f32>>> {
+    let mut generator = poisson.clone().build_chunked::<SmallRng, _>(seed, algo);
+    let mut result = vec![vec![]; order.len()];
+    // Generate in reverse to make sure the chunks don't depend on the order.
+    for (i, &(x, y)) in order.iter().enumerate().rev() {
+        result[i] = generator.chunk(x, y);
+    }
+    result
+}
+
+fn block(from: i32, to: i32) -> Vec<(i32, i32)> {
+    (from..to)
+        .flat_map(|y| (from..to).map(move |x| (x, y)))
+        .collect()
+}
+
+fn check_seamless<A: Creator>(poisson: Builder, algo: A) {
+    let order = block(-2, 3);
+    let width = 5.0 * poisson.dimensions().x;
+    let height = 5.0 * poisson.dimensions().y;
+    for seed in 0..3 {
+        let mut generator = poisson.clone().build_chunked::<SmallRng, _>(seed, algo);
+        let samples = order
+            .iter()
+            .flat_map(|&(x, y)| generator.chunk(x, y))
+            .map(|s| [s.x + 0.4 * width, s.y + 0.4 * height].into())
+            .collect::<Vec<mint::Vector2<f32>>>();
+        let whole = Builder::with_domain(width, height, poisson.radius(), Type::Normal);
+        let violations = validity::violations(&samples, &whole);
+        assert!(violations.is_empty(), "{:?}", violations);
+        // Chunks much larger than the disks always have room for samples of their own.
+        if poisson.dimensions().x.min(poisson.dimensions().y) >= 10.0 * poisson.radius() {
+            for &(x, y) in &order {
+                assert!(
+                    !generator.chunk(x, y).is_empty(),
+                    "Chunk ({}, {}) is empty.",
+                    x,
+                    y
+                );
+            }
+        }
+        if format!("{:?}", algo) == "Ebeida" {
+            let gaps = validity::gaps(&samples, &whole);
+            assert!(
+                gaps.iter().all(|g| g.distance < 2.5 * poisson.radius()),
+                "{:?}",
+                gaps.first()
+            );
+        }
+        assert_eq!(chunks(&poisson, seed, algo, &order), {
+            let mut generator = poisson.clone().build_chunked::<SmallRng, _>(seed, algo);
+            order
+                .iter()
+                .map(|&(x, y)| generator.chunk(x, y))
+                .collect::<Vec<_>>()
+        });
+    }
+}
+
+#[test]
+fn chunked_ebeida_seamless() {
+    check_seamless(
+        Builder::with_domain(1.0, 1.0, 0.05, Type::Normal),
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn chunked_bridson_seamless() {
+    check_seamless(
+        Builder::with_domain(1.0, 1.0, 0.05, Type::Normal),
+        algorithm::Bridson,
+    );
+}
+
+#[test]
+fn chunked_narrow_chunks() {
+    check_seamless(
+        Builder::with_domain(0.5, 0.1, 0.05, Type::Normal),
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn chunked_unload_regenerates_same() {
+    let mut generator = Builder::with_domain(1.0, 1.0, 0.05, Type::Normal)
+        .build_chunked::<SmallRng, _>(7, algorithm::Ebeida);
+    let first = generator.chunk(3, -5);
+    assert!(first
+        .iter()
+        .all(|s| 3.0 <= s.x && s.x < 4.0 && -5.0 <= s.y && s.y < -4.0));
+    generator.clear();
+    let _ = generator.chunk(4, -5);
+    generator.unload(3, -5);
+    assert_eq!(first, generator.chunk(3, -5));
+    let mut other = Builder::with_domain(1.0, 1.0, 0.05, Type::Normal)
+        .build_chunked::<SmallRng, _>(8, algorithm::Ebeida);
+    assert_ne!(first, other.chunk(3, -5));
+}
+
+#[test]
+fn chunked_radius_field_in_world_coordinates() {
+    let poisson = Builder::with_domain(1.0, 1.0, 0.02, Type::Normal).with_radius_field(0.06, |p| {
+        if p.x < 0.0 {
+            0.02
+        } else {
+            0.06
+        }
+    });
+    let mut generator = poisson.build_chunked::<SmallRng, _>(1, algorithm::Ebeida);
+    let left = generator.chunk(-1, 0).len();
+    let right = generator.chunk(0, 0).len();
+    assert!(
+        left > 4 * right,
+        "{} samples on the left and {} on the right.",
+        left,
+        right
+    );
+}
+
+#[test]
+#[should_panic]
+fn chunked_too_small_chunks() {
+    let _ = Builder::with_domain(0.05, 1.0, 0.05, Type::Normal)
+        .build_chunked::<SmallRng, _>(0, algorithm::Ebeida);
+}
@@ -1,4 +1,5 @@
-use poisson2d::{Builder, Type};
+use poisson2d::{algorithm, Builder, Error, Type};
+use rand::{rngs::SmallRng, SeedableRng};
 
 #[test]
 #[should_panic]
@@ -12,6 +13,117 @@ fn test_normal_too_large_radius() {
     let _ = Builder::with_radius(2f32.sqrt() / 2.0 + 0.0001, Type::Normal);
 }
 
+#[test]
+fn test_try_invalid_radius() {
+    let max = 2f32.sqrt() / 2.0;
+    for &radius in &[0.0, -1.0, max + 0.0001, f32::NAN] {
+        match Builder::try_with_radius(radius, Type::Normal) {
+            Err(Error::InvalidRadius { radius: r, max: m }) => {
+                assert!(r == radius || radius.is_nan());
+                assert_eq!(max, m);
+            }
+            other => panic!("{:?} was accepted as {:?}", radius, other),
+        }
+    }
+    assert_eq!(
+        Err(Error::InvalidRadius {
+            radius: 2.5001,
+            max: 2.5
+        }),
+        Builder::try_with_domain(4.0, 3.0, 2.5001, Type::Normal)
+    );
+    assert!(Builder::try_with_radius(max, Type::Normal).is_ok());
+}
+
+#[test]
+fn test_try_zero_samples() {
+    assert_eq!(
+        Err(Error::ZeroSamples),
+        Builder::try_with_samples(0, 0.8, Type::Periodic)
+    );
+    assert_eq!(
+        Err(Error::ZeroSamples),
+        Builder::try_with_domain_samples(2.0, 1.0, 0, 0.8, Type::Normal)
+    );
+}
+
+#[test]
+fn test_try_invalid_relative_radius() {
+    for &relative in &[0.0, -0.5, 1.000001] {
+        assert_eq!(
+            Err(Error::InvalidRelativeRadius(relative)),
+            Builder::try_with_relative_radius(relative, Type::Normal)
+        );
+        assert_eq!(
+            Err(Error::InvalidRelativeRadius(relative)),
+            Builder::try_with_samples(100, relative, Type::Normal)
+        );
+    }
+    assert_eq!(
+        Builder::with_samples(100, 1.0, Type::Normal),
+        Builder::try_with_samples(100, 1.0, Type::Normal).unwrap()
+    );
+}
+
+#[test]
+fn test_try_invalid_domain() {
+    for &(width, height) in &[(0.0, 1.0), (1.0, -1.0), (f32::INFINITY, 1.0)] {
+        assert_eq!(
+            Err(Error::InvalidDomain { width, height }),
+            Builder::try_with_domain(width, height, 0.1, Type::Normal)
+        );
+        assert_eq!(
+            Err(Error::InvalidDomain { width, height }),
+            Builder::try_with_domain_samples(width, height, 100, 0.8, Type::Periodic)
+        );
+    }
+}
+
+#[test]
+fn test_try_set_radius() {
+    let mut generator = Builder::with_samples(100, 0.8, Type::Normal)
+        .build(SmallRng::from_seed([0; 16]), algorithm::Ebeida);
+    let radius = generator.radius();
+    assert!(generator.try_set_radius(1.0).is_err());
+    assert!(generator.try_set_radius(0.0).is_err());
+    assert_eq!(radius, generator.radius());
+    assert!(generator.try_set_radius(0.05).is_ok());
+    assert_eq!(0.05, generator.radius());
+
+    let mut generator = Builder::with_radius(0.01, Type::Normal)
+        .with_radius_field(0.02, |_| 0.01)
+        .build(SmallRng::from_seed([0; 16]), algorithm::Ebeida);
+    assert_eq!(
+        Err(Error::InvalidRadius {
+            radius: 0.03,
+            max: 0.02
+        }),
+        generator.try_set_radius(0.03)
+    );
+}
+
+#[test]
+fn test_try_invalid_radius_field() {
+    let builder = Builder::with_radius(0.05, Type::Normal);
+    assert_eq!(
+        Err(Error::InvalidRadius {
+            radius: 0.05,
+            max: 0.04
+        }),
+        builder
+            .clone()
+            .try_with_radius_field(0.04, |_| 0.05)
+            .map(|_| ())
+    );
+    assert!(builder.try_with_radius_field(0.1, |_| 0.05).is_ok());
+}
+
+#[test]
+fn test_error_message() {
+    let error = Builder::try_with_relative_radius(2.0, Type::Normal).unwrap_err();
+    assert_eq!("relative radius 2 should be in (0, 1]", error.to_string());
+}
+
 // #[test]
 // #[should_panic]
 // fn test_calc_radius_too_small_alpha() {
@@ -0,0 +1,179 @@
+use glam::Vec2;
+use poisson2d::algorithm::{self, Creator};
+use poisson2d::delaunay::Triangulation;
+use poisson2d::sampling::Sampling;
+use poisson2d::Type::*;
+use poisson2d::{Builder, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn area(polygon: &[Vec2]) -> f32 {
+    (0..polygon.len())
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            a.x() * b.y() - a.y() * b.x()
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+fn circumcircle(a: Vec2, b: Vec2, c: Vec2) -> (Vec2, f32) {
+    let (b, c) = (b - a, c - a);
+    let d = 2.0 * (b.x() * c.y() - b.y() * c.x());
+    let center = Vec2::new(
+        c.y() * b.length_squared() - b.y() * c.length_squared(),
+        b.x() * c.length_squared() - c.x() * b.length_squared(),
+    ) / d;
+    (a + center, center.length())
+}
+
+fn distance(v1: Vec2, v2: Vec2, dimensions: Vec2, ptype: Type) -> f32 {
+    let mut diff = (v1 - v2).abs();
+    if let Periodic = ptype {
+        for n in 0..2 {
+            diff[n] = diff[n].min(dimensions[n] - diff[n]);
+        }
+    }
+    diff.length()
+}
+
+fn test_triangulation<A>(builder: Builder, algo: A)
+where
+    A: Creator,
+{
+    let dimensions: Vec2 = builder.dimensions().into();
+    let ptype = builder.poisson_type();
+    for seed in 0..3 {
+        let sampling = builder
+            .clone()
+            .build(SmallRng::from_seed([seed; 16]), algo)
+            .generate();
+        let triangulation = sampling.triangulate();
+        let triangles = triangulation.triangles();
+        if let Periodic = ptype {
+            // Euler characteristic of the torus.
+            assert_eq!(2 * sampling.len(), triangles.len());
+        }
+        let mut covered = 0.0;
+        for t in triangles {
+            let [a, b, c] = [0, 1, 2].map(|k| Vec2::from(triangulation.position(t[k])));
+            let size = area(&[a, b, c]);
+            assert!(size > 0.0, "Triangle {:?} isn't counter-clockwise.", t);
+            covered += size;
+            let (center, radius) = circumcircle(a, b, c);
+            for (i, &s) in sampling.iter().enumerate() {
+                if t.iter().any(|v| v.index == i) {
+                    continue;
+                }
+                let d = distance(s.into(), center, dimensions, ptype);
+                assert!(
+                    d >= radius * 0.999,
+                    "Sample {:?} is inside the circumcircle of {:?}.",
+                    s,
+                    t
+                );
+            }
+        }
+        if let Periodic = ptype {
+            let total = dimensions.x() * dimensions.y();
+            assert!(
+                (covered - total).abs() < total * 1e-3,
+                "Triangles cover {} of {}.",
+                covered,
+                total
+            );
+        }
+        test_voronoi(&sampling, &triangulation);
+    }
+}
+
+fn test_voronoi(sampling: &Sampling, triangulation: &Triangulation) {
+    let dimensions: Vec2 = sampling.dimensions().into();
+    let cells = triangulation.voronoi();
+    assert_eq!(sampling.len(), cells.len());
+    let mut covered = 0.0;
+    for (&s, cell) in sampling.iter().zip(&cells) {
+        let cell = cell.iter().map(|&v| Vec2::from(v)).collect::<Vec<_>>();
+        let s = Vec2::from(s);
+        for i in 0..cell.len() {
+            let (a, b) = (cell[i] - s, cell[(i + 1) % cell.len()] - s);
+            assert!(
+                a.x() * b.y() - a.y() * b.x() >= -1e-6,
+                "{:?} isn't inside its cell {:?}.",
+                s,
+                cell
+            );
+        }
+        covered += area(&cell);
+    }
+    let total = dimensions.x() * dimensions.y();
+    assert!(
+        (covered - total).abs() < total * 1e-3,
+        "Voronoi cells cover {} of {}.",
+        covered,
+        total
+    );
+}
+
+#[test]
+fn test_delaunay_normal() {
+    test_triangulation(Builder::with_radius(0.03, Normal), algorithm::Ebeida);
+    test_triangulation(Builder::with_radius(0.03, Normal), algorithm::Bridson);
+}
+
+#[test]
+fn test_delaunay_periodic() {
+    test_triangulation(Builder::with_radius(0.03, Periodic), algorithm::Ebeida);
+    test_triangulation(Builder::with_radius(0.03, Periodic), algorithm::Bridson);
+    test_triangulation(
+        Builder::with_samples(300, 0.8, Periodic),
+        algorithm::SampleElimination,
+    );
+}
+
+#[test]
+fn test_delaunay_rectangle() {
+    test_triangulation(
+        Builder::with_domain(3.0, 1.0, 0.04, Normal),
+        algorithm::Ebeida,
+    );
+    test_triangulation(
+        Builder::with_domain(1.0, 3.0, 0.04, Periodic),
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn test_delaunay_few_samples() {
+    for &ptype in &[Normal, Periodic] {
+        test_triangulation(Builder::with_radius(0.3, ptype), algorithm::Ebeida);
+    }
+    let sampling = Sampling::new(
+        &Builder::with_radius(0.1, Normal),
+        vec![[0.25, 0.5].into(), [0.75, 0.5].into()],
+    );
+    let triangulation = sampling.triangulate();
+    assert!(triangulation.triangles().is_empty());
+    test_voronoi(&sampling, &triangulation);
+}
+
+#[test]
+fn test_voronoi_periodic_neighbours_wrap_around() {
+    let builder = Builder::with_radius(0.1, Periodic);
+    let samples = (0..4)
+        .flat_map(|x| {
+            (0..4).map(move |y| {
+                [
+                    0.25 * x as f32 + 0.1,
+                    0.25 * y as f32 + 0.05 + 0.01 * x as f32,
+                ]
+                .into()
+            })
+        })
+        .collect::<Vec<_>>();
+    let sampling = Sampling::new(&builder, samples);
+    let triangulation = sampling.triangulate();
+    let near = triangulation.neighbours(0);
+    assert!(near.iter().any(|v| v.index == 12 && v.shift == [-1, 0]));
+    assert!(near.iter().any(|v| v.index == 3 && v.shift == [0, -1]));
+    test_voronoi(&sampling, &triangulation);
+}
@@ -0,0 +1,71 @@
+use poisson2d::Type::*;
+use poisson2d::{algorithm, Builder};
+use rand::{rngs::SmallRng, SeedableRng};
+
+use crate::helper::test_with_domain_samples;
+
+mod helper;
+
+#[test]
+fn test_domain_wide_normal() {
+    test_with_domain_samples(4.0, 1.0, 100, 0.8, 100, Normal);
+}
+
+#[test]
+fn test_domain_wide_periodic() {
+    test_with_domain_samples(4.0, 1.0, 100, 0.8, 50, Periodic);
+}
+
+#[test]
+fn test_domain_tall_normal() {
+    test_with_domain_samples(0.5, 3.0, 100, 0.8, 100, Normal);
+}
+
+#[test]
+fn test_domain_tall_periodic() {
+    test_with_domain_samples(0.5, 3.0, 100, 0.8, 50, Periodic);
+}
+
+#[test]
+fn test_domain_large_normal() {
+    test_with_domain_samples(4096.0, 1024.0, 100, 0.8, 50, Normal);
+}
+
+#[test]
+fn test_domain_fills_whole_rectangle() {
+    let rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let samples = Builder::with_domain(4.0, 1.0, 0.05, Normal)
+        .build(rand, algorithm::Ebeida)
+        .generate();
+    assert!(samples.iter().any(|s| s.x > 3.9));
+    assert!(samples.iter().any(|s| s.y > 0.9));
+}
+
+#[test]
+fn test_domain_sample_count_is_close() {
+    for &(width, height) in &[(1.0, 1.0), (4.0, 1.0), (1.0, 8.0)] {
+        let rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let samples = Builder::with_domain_samples(width, height, 500, 0.8, Normal)
+            .build(rand, algorithm::Ebeida)
+            .generate();
+        assert!(
+            400 < samples.len() && samples.len() < 600,
+            "{}x{} produced {} samples",
+            width,
+            height,
+            samples.len()
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_domain_too_large_radius() {
+    let _ = Builder::with_domain(4.0, 3.0, 2.5001, Normal);
+}
+
+#[test]
+#[should_panic]
+fn test_domain_empty() {
+    let _ = Builder::with_domain(0.0, 3.0, 0.1, Normal);
+}
@@ -0,0 +1,154 @@
+use poisson2d::{algorithm, validity, Builder, Error, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn stats_of(
+    builder: &Builder,
+    config: algorithm::EbeidaConfig,
+    seed: u64,
+) -> (usize, algorithm::EbeidaStats) {
+    let mut iter = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(seed), config)
+        .into_iter();
+    let samples = iter.by_ref().count();
+    (samples, iter.algorithm().stats().clone())
+}
+
+#[test]
+fn stats_add_up() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_radius(0.02, ptype);
+        for &exact in &[false, true] {
+            for seed in 0..3 {
+                let config = algorithm::EbeidaConfig::new().with_exact(exact);
+                let (samples, stats) = stats_of(&builder, config, seed);
+                assert_eq!(samples, stats.throws - stats.rejections);
+                assert_eq!(stats.subdivisions.len() + 1, stats.active.len());
+                for (level, &subdivided) in stats.subdivisions.iter().enumerate() {
+                    assert!(subdivided <= stats.active[level]);
+                    assert!(stats.active[level + 1] <= 4 * subdivided);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn max_level_limits_subdivisions() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let (full, _) = stats_of(&builder, algorithm::EbeidaConfig::new(), 0);
+    for &max_level in &[1, 3] {
+        let config = algorithm::EbeidaConfig::new().with_max_level(max_level);
+        let (samples, stats) = stats_of(&builder, config, 0);
+        assert!(stats.subdivisions.len() <= max_level);
+        // Cells left at the last level still get samples, so the sampling only ends up slightly sparser.
+        assert!(
+            samples as f32 > 0.9 * full as f32,
+            "{} of {}",
+            samples,
+            full
+        );
+        let samples = builder
+            .clone()
+            .build(SmallRng::seed_from_u64(0), config)
+            .generate();
+        assert!(validity::violations(&samples, &builder).is_empty());
+    }
+}
+
+#[test]
+fn max_level_is_capped_on_large_grids() {
+    let max_level = |builder: Builder, max_level: usize| {
+        let config = algorithm::EbeidaConfig::new().with_max_level(max_level);
+        builder
+            .build(SmallRng::seed_from_u64(0), config)
+            .into_iter()
+            .algorithm()
+            .max_level()
+    };
+    // 71 cells along each side fit into 7 bits.
+    assert_eq!(24, max_level(Builder::with_radius(0.01, Type::Normal), 30));
+    assert_eq!(5, max_level(Builder::with_radius(0.01, Type::Normal), 5));
+    // 142 cells along each side take 8 bits.
+    assert_eq!(23, max_level(Builder::with_radius(0.005, Type::Normal), 30));
+    // 2263 cells along the longer side take 12 bits.
+    let builder = Builder::with_domain(16.0, 1.0, 0.005, Type::Periodic);
+    assert_eq!(19, max_level(builder.clone(), 30));
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(0), algorithm::Ebeida)
+        .generate();
+    assert!(validity::violations(&samples, &builder).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn zero_max_level() {
+    let _ = algorithm::EbeidaConfig::new().with_max_level(0);
+}
+
+#[test]
+fn try_max_level() {
+    assert_eq!(
+        Err(Error::ZeroMaxLevel),
+        algorithm::EbeidaConfig::new().try_with_max_level(0)
+    );
+    assert_eq!(
+        Ok(algorithm::EbeidaConfig::new().with_max_level(3)),
+        algorithm::EbeidaConfig::new().try_with_max_level(3)
+    );
+}
+
+#[test]
+fn occupied_cells_are_not_counted_as_throws() {
+    // 8 cells along each side, each of which gets a sample at its centre.
+    let builder = Builder::with_radius(0.1, Type::Normal);
+    let mut iter = builder
+        .build(SmallRng::seed_from_u64(0), algorithm::Ebeida)
+        .into_iter();
+    for x in 0..8 {
+        for y in 0..8 {
+            iter.restrict([(x as f32 + 0.5) / 8.0, (y as f32 + 0.5) / 8.0].into());
+        }
+    }
+    assert_eq!(0, iter.by_ref().count());
+    let stats = iter.algorithm().stats();
+    assert_eq!(0, stats.throws);
+    assert_eq!(0, stats.rejections);
+}
+
+#[test]
+fn throw_factor_changes_throws() {
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let (_, few) = stats_of(
+        &builder,
+        algorithm::EbeidaConfig::new().with_throw_factor(0.1),
+        0,
+    );
+    let (_, many) = stats_of(
+        &builder,
+        algorithm::EbeidaConfig::new().with_throw_factor(3.0),
+        0,
+    );
+    assert_eq!(few.active[0], many.active[0]);
+    assert!(few.active[1] > many.active[1]);
+}
+
+#[test]
+#[should_panic]
+fn zero_throw_factor() {
+    let _ = algorithm::EbeidaConfig::new().with_throw_factor(0.0);
+}
+
+#[test]
+fn try_throw_factor() {
+    for &throw_factor in &[0.0, -1.0, f64::INFINITY, f64::NAN] {
+        match algorithm::EbeidaConfig::new().try_with_throw_factor(throw_factor) {
+            Err(Error::InvalidThrowFactor(_)) => {}
+            result => panic!("{} gave {:?}.", throw_factor, result),
+        }
+    }
+    assert!(algorithm::EbeidaConfig::new()
+        .try_with_throw_factor(0.5)
+        .is_ok());
+}
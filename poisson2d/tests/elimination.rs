@@ -0,0 +1,182 @@
+use glam::Vec2;
+use poisson2d::polygon::Polygon;
+use poisson2d::Type::*;
+use poisson2d::{algorithm, Builder, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn generate(builder: &Builder, seed: u8) -> Vec<Vec2> {
+    let rand = SmallRng::from_seed([seed; 16]);
+    builder
+        .clone()
+        .build(rand, algorithm::SampleElimination)
+        .generate()
+        .into_iter()
+        .map(Vec2::from)
+        .collect()
+}
+
+/// Smallest distance between two samples relative to the largest radius possible for that many samples.
+fn relative_radius(samples: &[Vec2], dimensions: Vec2, ptype: Type) -> f32 {
+    let mut min = f32::MAX;
+    for (i, &v1) in samples.iter().enumerate() {
+        for &v2 in &samples[i + 1..] {
+            let mut diff = v1 - v2;
+            if let Periodic = ptype {
+                for n in 0..2 {
+                    diff[n] = diff[n].abs().min(dimensions[n] - diff[n].abs());
+                }
+            }
+            min = min.min(diff.length());
+        }
+    }
+    let area = dimensions.x() * dimensions.y();
+    let max_radius = (area / (2.0 * 3f32.sqrt() * samples.len() as f32)).sqrt();
+    min / (2.0 * max_radius)
+}
+
+fn test_exact(width: f32, height: f32, samples: usize, ptype: Type, seeds: u8) {
+    let builder = Builder::with_domain_samples(width, height, samples, 0.8, ptype);
+    let dimensions = Vec2::new(width, height);
+    for seed in 0..seeds {
+        let result = generate(&builder, seed);
+        assert_eq!(samples, result.len());
+        for v in &result {
+            assert!(v.x() >= 0.0 && v.x() < width);
+            assert!(v.y() >= 0.0 && v.y() < height);
+        }
+        let relative = relative_radius(&result, dimensions, ptype);
+        assert!(
+            relative > 0.6,
+            "Samples were only {} of the largest radius apart.",
+            relative
+        );
+    }
+}
+
+#[test]
+fn test_elimination_exact_normal() {
+    test_exact(1.0, 1.0, 500, Normal, 10);
+}
+
+#[test]
+fn test_elimination_exact_periodic() {
+    test_exact(1.0, 1.0, 500, Periodic, 10);
+}
+
+#[test]
+fn test_elimination_exact_rectangle() {
+    test_exact(4.0, 1.0, 317, Periodic, 5);
+    test_exact(1.0, 3.0, 1, Normal, 5);
+}
+
+#[test]
+fn test_elimination_size_hint_is_exact() {
+    let builder = Builder::with_samples(100, 0.8, Normal);
+    let mut iter = builder
+        .build(SmallRng::from_seed([7; 16]), algorithm::SampleElimination)
+        .into_iter();
+    assert_eq!((100, Some(100)), iter.size_hint());
+    let mut remaining = 100;
+    while iter.next().is_some() {
+        remaining -= 1;
+        assert_eq!((remaining, Some(remaining)), iter.size_hint());
+    }
+    assert_eq!(0, remaining);
+}
+
+#[test]
+fn test_elimination_with_radius() {
+    let builder = Builder::with_radius(0.05, Periodic);
+    let first = generate(&builder, 1);
+    assert!(!first.is_empty());
+    assert_eq!(first.len(), generate(&builder, 2).len());
+}
+
+#[test]
+fn test_elimination_polygon() {
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [0.5, 1.0].into(),
+    ]);
+    let builder = Builder::with_samples(200, 0.8, Normal).with_polygon(polygon.clone());
+    for seed in 0..5 {
+        let result = generate(&builder, seed);
+        assert_eq!(200, result.len());
+        for v in result {
+            assert!(polygon.contains(v.into()));
+        }
+    }
+}
+
+#[test]
+fn test_elimination_keeps_restricted() {
+    let builder = Builder::with_samples(50, 0.8, Periodic);
+    let mut iter = builder
+        .build(SmallRng::from_seed([3; 16]), algorithm::SampleElimination)
+        .into_iter();
+    iter.restrict([0.5, 0.5].into());
+    let result = iter.map(Vec2::from).collect::<Vec<_>>();
+    assert_eq!(50, result.len());
+    let closest = result
+        .iter()
+        .map(|v| (*v - Vec2::new(0.5, 0.5)).length())
+        .fold(f32::MAX, f32::min);
+    // Candidates right next to the restricted sample weigh the most and get eliminated first.
+    assert!(
+        closest > 0.05,
+        "Closest sample to the restricted one was {} away.",
+        closest
+    );
+}
+
+#[test]
+fn test_elimination_keeps_restricted_outside_of_domain() {
+    let builder = Builder::with_samples(50, 0.8, Normal);
+    let restricted = Vec2::new(-0.01, 0.5);
+    let mut iter = builder
+        .build(SmallRng::from_seed([3; 16]), algorithm::SampleElimination)
+        .into_iter();
+    iter.restrict(restricted.into());
+    let result = iter.map(Vec2::from).collect::<Vec<_>>();
+    assert_eq!(50, result.len());
+    let closest = result
+        .iter()
+        .map(|v| (*v - restricted).length())
+        .fold(f32::MAX, f32::min);
+    assert!(
+        closest > 0.05,
+        "Closest sample to the restricted one was {} away.",
+        closest
+    );
+}
+
+#[test]
+fn test_elimination_every_sample_stays_legal() {
+    let builder = Builder::with_samples(50, 0.8, Normal);
+    let mut iter = builder
+        .build(SmallRng::from_seed([5; 16]), algorithm::SampleElimination)
+        .into_iter();
+    iter.restrict([0.5, 0.5].into());
+    let first = iter.next().unwrap();
+    assert!(iter.stays_legal([0.5, 0.5].into()));
+    assert!(iter.stays_legal(first));
+}
+
+#[test]
+fn test_elimination_keeps_every_candidate_of_small_polygon() {
+    // The polygon is so small that fewer candidates than samples are found in it.
+    let polygon = Polygon::new(vec![
+        [0.5, 0.5].into(),
+        [0.501, 0.5].into(),
+        [0.5, 0.502].into(),
+    ]);
+    let builder = Builder::with_samples(200, 0.8, Normal).with_polygon(polygon.clone());
+    for seed in 0..5 {
+        let result = generate(&builder, seed);
+        assert!(result.len() < 200);
+        for v in result {
+            assert!(polygon.contains(v.into()));
+        }
+    }
+}
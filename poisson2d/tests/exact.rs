@@ -0,0 +1,112 @@
+use poisson2d::algorithm::{self, Creator};
+use poisson2d::polygon::Polygon;
+use poisson2d::validity;
+use poisson2d::{Builder, Error, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn check_exact<A: Creator>(poisson: Builder, algo: A) {
+    for seed in 0..4u8 {
+        for &samples in &[1, 17, 100, 333] {
+            let rng = SmallRng::from_seed([seed; 16]);
+            let (sampling, radius) = poisson
+                .clone()
+                .build(rng.clone(), algo)
+                .generate_exact(samples)
+                .unwrap();
+            assert_eq!(
+                samples,
+                sampling.len(),
+                "{:?} with seed {}",
+                poisson.poisson_type(),
+                seed
+            );
+            assert!(radius > 0.0);
+            let dimensions = poisson.dimensions();
+            let used =
+                Builder::with_domain(dimensions.x, dimensions.y, radius, poisson.poisson_type());
+            assert!(validity::violations(&sampling, &used).is_empty());
+            let (again, same) = poisson
+                .clone()
+                .build(rng, algo)
+                .generate_exact(samples)
+                .unwrap();
+            assert_eq!(radius, same);
+            assert_eq!(&sampling[..], &again[..]);
+        }
+    }
+}
+
+#[test]
+fn exact_bridson_normal() {
+    check_exact(
+        Builder::with_samples(100, 0.8, Type::Normal),
+        algorithm::Bridson,
+    );
+}
+
+#[test]
+fn exact_bridson_periodic() {
+    check_exact(
+        Builder::with_samples(100, 0.8, Type::Periodic),
+        algorithm::Bridson,
+    );
+}
+
+#[test]
+fn exact_ebeida_normal() {
+    check_exact(
+        Builder::with_samples(100, 0.8, Type::Normal),
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn exact_ebeida_periodic() {
+    check_exact(
+        Builder::with_samples(100, 0.8, Type::Periodic),
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn exact_domain_and_polygon() {
+    check_exact(
+        Builder::with_domain(3.0, 1.0, 0.2, Type::Periodic),
+        algorithm::Ebeida,
+    );
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [0.5, 1.0].into(),
+    ]);
+    check_exact(
+        Builder::with_radius(0.1, Type::Normal).with_polygon(polygon),
+        algorithm::Bridson,
+    );
+}
+
+#[test]
+fn exact_too_many_samples() {
+    let polygon = Polygon::new(vec![
+        [2.0, 2.0].into(),
+        [3.0, 2.0].into(),
+        [3.0, 3.0].into(),
+    ]);
+    let generator = Builder::with_radius(0.1, Type::Normal)
+        .with_polygon(polygon)
+        .build(SmallRng::from_seed([0; 16]), algorithm::Ebeida);
+    assert_eq!(
+        Error::TooManySamples {
+            requested: 5,
+            max: 0
+        },
+        generator.generate_exact(5).unwrap_err()
+    );
+}
+
+#[test]
+fn exact_zero_samples() {
+    let generator = Builder::with_radius(0.1, Type::Normal)
+        .build(SmallRng::from_seed([0; 16]), algorithm::Bridson);
+    assert_eq!(Error::ZeroSamples, generator.generate_exact(0).unwrap_err());
+}
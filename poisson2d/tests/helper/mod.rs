@@ -34,6 +34,78 @@ pub fn test_with_samples(samples: usize, relative_radius: f32, seeds: u32, ptype
     );
 }
 
+pub fn test_with_domain_samples(
+    width: f32,
+    height: f32,
+    samples: usize,
+    relative_radius: f32,
+    seeds: u32,
+    ptype: Type,
+) {
+    let builder = Builder::with_domain_samples(width, height, samples, relative_radius, ptype);
+    let mut prefiller = |_| |_| None;
+    test_algo(
+        &builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::Ebeida,
+    );
+    test_algo(
+        &builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::EbeidaConfig::new().with_exact(true),
+    );
+    test_algo(
+        &builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::Bridson,
+    );
+    test_algo(
+        &builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::BridsonConfig::new().with_fill_gaps(true),
+    );
+}
+
+pub fn test_with_builder(builder: &Builder, seeds: u32) {
+    let mut prefiller = |_| |_| None;
+    test_algo(
+        builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::Ebeida,
+    );
+    test_algo(
+        builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::EbeidaConfig::new().with_exact(true),
+    );
+    test_algo(
+        builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::Bridson,
+    );
+    test_algo(
+        builder,
+        seeds,
+        &mut prefiller,
+        When::Always,
+        algorithm::BridsonConfig::new().with_fill_gaps(true),
+    );
+}
+
 pub fn test_with_samples_prefilled<F, I>(
     samples: usize,
     relative_radius: f32,
@@ -45,35 +117,27 @@ pub fn test_with_samples_prefilled<F, I>(
     F: FnMut(f32) -> I,
     I: FnMut(Option<Vec2>) -> Option<Vec2>,
 {
+    let builder = Builder::with_samples(samples, relative_radius, ptype);
+    test_algo(&builder, seeds, &mut prefiller, valid, algorithm::Ebeida);
     test_algo(
-        samples,
-        relative_radius,
+        &builder,
         seeds,
-        ptype,
         &mut prefiller,
         valid,
-        algorithm::Ebeida,
+        algorithm::EbeidaConfig::new().with_exact(true),
     );
+    test_algo(&builder, seeds, &mut prefiller, valid, algorithm::Bridson);
     test_algo(
-        samples,
-        relative_radius,
+        &builder,
         seeds,
-        ptype,
         &mut prefiller,
         valid,
-        algorithm::Bridson,
+        algorithm::BridsonConfig::new().with_fill_gaps(true),
     );
 }
 
-fn test_algo<F, I, A>(
-    samples: usize,
-    relative_radius: f32,
-    seeds: u32,
-    ptype: Type,
-    prefiller: &mut F,
-    valid: When,
-    algo: A,
-) where
+fn test_algo<F, I, A>(builder: &Builder, seeds: u32, prefiller: &mut F, valid: When, algo: A)
+where
     F: FnMut(f32) -> I,
     I: FnMut(Option<Vec2>) -> Option<Vec2>,
     A: algorithm::Creator,
@@ -99,9 +163,7 @@ fn test_algo<F, I, A>(
             (i * 113 + 2539) as u8,
             (i * 131 + 2521) as u8,
         ]);
-        let mut poisson_iter = Builder::with_samples(samples, relative_radius, ptype)
-            .build(rand, algo)
-            .into_iter();
+        let mut poisson_iter = builder.clone().build(rand, algo).into_iter();
         let mut poisson = vec![];
         let mut prefill = (prefiller)(poisson_iter.radius());
         let mut last = None;
@@ -137,26 +199,22 @@ fn test_algo<F, I, A>(
                 break;
             }
         }
-        let radius = poisson_iter.radius();
-        let poisson_type = poisson_iter.poisson_type();
-        let poisson = poisson.into_iter().chain(
-            if let Always = valid {
-                prefilled
-            } else {
-                vec![]
-            }
-            .into_iter(),
-        );
-        test_poisson(poisson, radius, poisson_type, algo, does_prefill);
+        let poisson = poisson.into_iter().chain(if let Always = valid {
+            prefilled
+        } else {
+            vec![]
+        });
+        test_poisson(poisson, builder, algo, does_prefill);
     }
 }
 
-pub fn test_poisson<I, A>(poisson: I, radius: f32, poisson_type: Type, algo: A, does_prefill: bool)
+pub fn test_poisson<I, A>(poisson: I, builder: &Builder, algo: A, does_prefill: bool)
 where
     I: Iterator<Item = Vec2>,
     A: algorithm::Creator,
 {
     use poisson2d::Type::*;
+    let dimensions: Vec2 = builder.dimensions().into();
     let mut vecs = vec![];
     let mut hints = vec![];
     {
@@ -185,12 +243,19 @@ where
         for v in &vecs {
             for n in 0..2 {
                 assert!(v[n] >= 0.0);
-                assert!(v[n] < 1.0);
+                assert!(v[n] < dimensions[n]);
+            }
+            if let Some(polygon) = builder.polygon() {
+                assert!(polygon.contains((*v).into()));
             }
         }
     }
 
-    let vecs = match poisson_type {
+    let vecs = vecs
+        .into_iter()
+        .map(|v| (v, builder.radius_at(v.into())))
+        .collect::<Vec<_>>();
+    let vecs = match builder.poisson_type() {
         Periodic => {
             let mut vecs2 = vec![];
             for n in 0..9i64 {
@@ -199,10 +264,10 @@ where
                 for i in 0..2 {
                     let rem = div % 3;
                     div /= 3;
-                    t[i] = (rem - 1) as f32;
+                    t[i] = (rem - 1) as f32 * dimensions[i];
                 }
-                for v in &vecs {
-                    vecs2.push(*v + t);
+                for &(v, r) in &vecs {
+                    vecs2.push((v + t, r));
                 }
             }
             vecs2
@@ -211,26 +276,26 @@ where
     };
 
     //TODO: Figure out how to check if distribution is maximal.
-    assert_legal_poisson(&vecs, radius, algo);
+    assert_legal_poisson(&vecs, algo);
 }
 
-pub fn assert_legal_poisson<A>(vecs: &Vec<Vec2>, radius: f32, algo: A)
+pub fn assert_legal_poisson<A>(vecs: &[(Vec2, f32)], algo: A)
 where
     A: algorithm::Creator,
 {
-    for &v1 in vecs {
-        for &v2 in vecs {
+    for &(v1, r1) in vecs {
+        for &(v2, r2) in vecs {
             if v1 == v2 {
                 continue;
             }
             let dist = (v1 - v2).length();
-            assert!(dist > radius * 2.0,
+            assert!(dist > r1 + r2,
                     "Poisson disk sampling requirement not met while generating using the '{:?}' algorithm: There exists 2 vectors with \
                      distance to each other of {} which is smaller than smallest allowed one {}. \
                      The samples: [{:?}, {:?}]",
                     algo,
                     dist as f64,
-                    (radius as f64) * 2.0,
+                    (r1 + r2) as f64,
                     v1,
                     v2);
         }
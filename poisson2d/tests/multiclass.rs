@@ -0,0 +1,168 @@
+use glam::Vec2;
+use poisson2d::multiclass::Classes;
+use poisson2d::polygon::Polygon;
+use poisson2d::{Builder, Error, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn generate(builder: Builder, classes: &Classes, seed: u8) -> Vec<(usize, Vec2)> {
+    let rand = SmallRng::from_seed([seed; 16]);
+    builder
+        .build_with_classes(rand, classes.clone())
+        .generate()
+        .into_iter()
+        .map(|(c, v)| (c, v.into()))
+        .collect()
+}
+
+fn assert_legal_classes(
+    samples: &[(usize, Vec2)],
+    classes: &Classes,
+    dimensions: Vec2,
+    ptype: Type,
+) {
+    for (i, &(c1, v1)) in samples.iter().enumerate() {
+        for &(c2, v2) in &samples[i + 1..] {
+            let mut diff = v1 - v2;
+            if let Type::Periodic = ptype {
+                for n in 0..2 {
+                    diff[n] = diff[n].abs().min(dimensions[n] - diff[n].abs());
+                }
+            }
+            let dist = diff.length();
+            let min = 2.0 * classes.radius(c1, c2);
+            assert!(
+                dist >= min,
+                "Samples {:?} of class {} and {:?} of class {} are {} apart, which is less than {}.",
+                v1,
+                c1,
+                v2,
+                c2,
+                dist,
+                min
+            );
+        }
+    }
+}
+
+fn test_classes(builder: Builder, classes: &Classes, seeds: u8) {
+    let dimensions: Vec2 = builder.dimensions().into();
+    let ptype = builder.poisson_type();
+    for seed in 0..seeds {
+        let samples = generate(builder.clone(), classes, seed);
+        assert!(!samples.is_empty());
+        for &(c, v) in &samples {
+            assert!(c < classes.count());
+            assert!(v.x() >= 0.0 && v.x() < dimensions.x());
+            assert!(v.y() >= 0.0 && v.y() < dimensions.y());
+        }
+        assert_legal_classes(&samples, classes, dimensions, ptype);
+    }
+}
+
+#[test]
+fn test_multiclass_normal() {
+    let classes = Classes::from_radii(&[0.05, 0.02, 0.01]);
+    test_classes(Builder::with_radius(0.01, Type::Normal), &classes, 10);
+}
+
+#[test]
+fn test_multiclass_periodic() {
+    let classes = Classes::from_radii(&[0.05, 0.02, 0.01]);
+    test_classes(Builder::with_radius(0.01, Type::Periodic), &classes, 10);
+}
+
+#[test]
+fn test_multiclass_matrix_in_domain() {
+    let classes = Classes::new(vec![vec![0.04, 0.03], vec![0.03, 0.02]]);
+    test_classes(
+        Builder::with_domain(3.0, 1.0, 0.02, Type::Periodic),
+        &classes,
+        5,
+    );
+}
+
+#[test]
+fn test_multiclass_polygon() {
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [0.5, 1.0].into(),
+    ]);
+    let builder = Builder::with_radius(0.01, Type::Normal).with_polygon(polygon.clone());
+    let classes = Classes::from_radii(&[0.03, 0.01]);
+    for seed in 0..5 {
+        for (_, v) in generate(builder.clone(), &classes, seed) {
+            assert!(polygon.contains(v.into()));
+        }
+    }
+}
+
+#[test]
+fn test_multiclass_every_class_is_sampled() {
+    let classes = Classes::from_radii(&[0.04, 0.02, 0.01]);
+    let samples = generate(Builder::with_radius(0.01, Type::Normal), &classes, 7);
+    let mut counts = [0usize; 3];
+    for &(c, _) in &samples {
+        counts[c] += 1;
+    }
+    // Denser classes should get more samples and each class alone should be close to maximal.
+    assert!(
+        counts[0] < counts[1] && counts[1] < counts[2],
+        "{:?}",
+        counts
+    );
+    for c in 0..3 {
+        let own = samples
+            .iter()
+            .filter(|s| s.0 == c)
+            .map(|s| s.1)
+            .collect::<Vec<_>>();
+        let expected = 1.0 / (classes.radius(c, c) * classes.radius(c, c) * 12f32.sqrt());
+        assert!(
+            own.len() as f32 > 0.3 * expected,
+            "Class {} only has {} samples while roughly {} were expected.",
+            c,
+            own.len(),
+            expected
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_multiclass_asymmetric_matrix() {
+    let _ = Classes::new(vec![vec![0.04, 0.03], vec![0.02, 0.02]]);
+}
+
+#[test]
+#[should_panic]
+fn test_multiclass_radius_field() {
+    let _ = Builder::with_radius(0.01, Type::Normal)
+        .with_radius_field(0.02, |_| 0.01)
+        .build_with_classes(SmallRng::from_seed([0; 16]), Classes::from_radii(&[0.01]));
+}
+
+#[test]
+fn test_multiclass_try_radius_field() {
+    let result = Builder::with_radius(0.01, Type::Normal)
+        .with_radius_field(0.02, |_| 0.01)
+        .try_build_with_classes(SmallRng::from_seed([0; 16]), Classes::from_radii(&[0.01]));
+    assert_eq!(Some(Error::UnsupportedRadiusField), result.err());
+}
+
+#[test]
+fn test_multiclass_seed() {
+    let classes = Classes::from_radii(&[0.05, 0.02]);
+    let seeds = [(0, Vec2::new(0.5, 0.5)), (1, Vec2::new(0.2, 0.8))];
+    for seed in 0..5 {
+        let mut iter = Builder::with_radius(0.02, Type::Normal)
+            .build_with_classes(SmallRng::from_seed([seed; 16]), classes.clone())
+            .into_iter();
+        for &(c, v) in &seeds {
+            iter.seed(c, v.into());
+        }
+        let mut samples = iter.map(|(c, v)| (c, v.into())).collect::<Vec<_>>();
+        samples.extend_from_slice(&seeds);
+        assert_legal_classes(&samples, &classes, Vec2::one(), Type::Normal);
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "rayon")]
+
+use poisson2d::algorithm::{self, Creator};
+use poisson2d::polygon::Polygon;
+use poisson2d::sampling::Sampling;
+use poisson2d::validity;
+use poisson2d::{Builder, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn generate<A: Creator + Sync>(poisson: &Builder, algo: A, seed: u8, threads: usize) -> Sampling {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap();
+    pool.install(|| {
+        poisson
+            .clone()
+            .build(SmallRng::from_seed([seed; 16]), algo)
+            .generate_parallel()
+    })
+}
+
+fn check_parallel<A: Creator + Sync>(poisson: Builder, algo: A, maximal: bool) {
+    for seed in 0..3 {
+        let sampling = generate(&poisson, algo, seed, 4);
+        let violations = validity::violations(&sampling, &poisson);
+        assert!(violations.is_empty(), "{:?}", violations);
+        if maximal {
+            let gaps = validity::gaps(&sampling, &poisson);
+            assert!(
+                gaps.iter().all(|g| g.distance < 2.5 * poisson.radius()),
+                "{:?}",
+                gaps.first()
+            );
+        }
+        assert_eq!(&sampling[..], &generate(&poisson, algo, seed, 1)[..]);
+        assert_eq!(&sampling[..], &generate(&poisson, algo, seed, 3)[..]);
+    }
+}
+
+#[test]
+fn parallel_ebeida_normal() {
+    check_parallel(
+        Builder::with_domain(3.0, 2.0, 0.01, Type::Normal),
+        algorithm::Ebeida,
+        true,
+    );
+}
+
+#[test]
+fn parallel_ebeida_periodic() {
+    check_parallel(
+        Builder::with_domain(3.0, 2.0, 0.01, Type::Periodic),
+        algorithm::Ebeida,
+        true,
+    );
+}
+
+#[test]
+fn parallel_bridson() {
+    check_parallel(
+        Builder::with_domain(3.0, 2.0, 0.01, Type::Normal),
+        algorithm::Bridson,
+        false,
+    );
+    check_parallel(
+        Builder::with_domain(3.0, 2.0, 0.01, Type::Periodic),
+        algorithm::Bridson,
+        false,
+    );
+}
+
+#[test]
+fn parallel_polygon() {
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [3.0, 0.0].into(),
+        [1.5, 2.0].into(),
+    ]);
+    let poisson = Builder::with_domain(3.0, 2.0, 0.01, Type::Normal).with_polygon(polygon.clone());
+    let sampling = generate(&poisson, algorithm::Ebeida, 0, 4);
+    assert!(sampling.iter().all(|&s| polygon.contains(s)));
+    assert!(validity::violations(&sampling, &poisson).is_empty());
+}
+
+#[test]
+fn parallel_small_area_falls_back() {
+    let poisson = Builder::with_radius(0.05, Type::Periodic);
+    let sequential = poisson
+        .clone()
+        .build(SmallRng::from_seed([1; 16]), algorithm::Ebeida)
+        .generate();
+    assert_eq!(
+        &sequential[..],
+        &generate(&poisson, algorithm::Ebeida, 1, 4)[..]
+    );
+}
@@ -0,0 +1,157 @@
+use poisson2d::polygon::Polygon;
+use poisson2d::Type::*;
+use poisson2d::{algorithm, validity, Builder};
+use rand::{rngs::SmallRng, SeedableRng};
+
+use crate::helper::test_with_builder;
+
+mod helper;
+
+fn island() -> Polygon {
+    Polygon::new(vec![
+        [0.1, 0.1].into(),
+        [0.9, 0.2].into(),
+        [0.7, 0.5].into(),
+        [0.9, 0.9].into(),
+        [0.2, 0.8].into(),
+    ])
+}
+
+fn room() -> Polygon {
+    Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [4.0, 0.0].into(),
+        [4.0, 1.0].into(),
+        [0.0, 1.0].into(),
+    ])
+    .with_hole(vec![
+        [1.0, 0.25].into(),
+        [3.0, 0.25].into(),
+        [3.0, 0.75].into(),
+        [1.0, 0.75].into(),
+    ])
+}
+
+#[test]
+fn test_polygon_concave_normal() {
+    let builder = Builder::with_samples(100, 0.8, Normal).with_polygon(island());
+    test_with_builder(&builder, 100);
+}
+
+#[test]
+fn test_polygon_concave_periodic() {
+    let builder = Builder::with_samples(100, 0.8, Periodic).with_polygon(island());
+    test_with_builder(&builder, 100);
+}
+
+#[test]
+fn test_polygon_with_hole_normal() {
+    let builder = Builder::with_domain(4.0, 1.0, 0.05, Normal).with_polygon(room());
+    test_with_builder(&builder, 20);
+}
+
+#[test]
+fn test_polygon_with_hole_periodic() {
+    let builder = Builder::with_domain(4.0, 1.0, 0.05, Periodic).with_polygon(room());
+    test_with_builder(&builder, 20);
+}
+
+#[test]
+fn test_polygon_outside_of_domain_generates_nothing() {
+    let polygon = Polygon::new(vec![
+        [2.0, 2.0].into(),
+        [3.0, 2.0].into(),
+        [3.0, 3.0].into(),
+    ]);
+    let builder = Builder::with_radius(0.05, Normal).with_polygon(polygon);
+    let rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    assert!(builder
+        .clone()
+        .build(rand.clone(), algorithm::Ebeida)
+        .generate()
+        .is_empty());
+    assert!(builder
+        .build(rand, algorithm::Bridson)
+        .generate()
+        .is_empty());
+}
+
+#[test]
+fn test_polygon_edges_are_filled() {
+    let radius = 0.02;
+    let builder = Builder::with_domain(4.0, 1.0, radius, Normal).with_polygon(room());
+    let rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let samples = builder.build(rand, algorithm::Ebeida).generate();
+    // Cells straddling the edge of the hole must not be discarded, so the edge should be filled as well as the interior.
+    for i in 0..100 {
+        let x = 1.0 + 2.0 * (i as f32 + 0.5) / 100.0;
+        let edge = [x, 0.25 - 1e-3];
+        assert!(
+            samples
+                .iter()
+                .any(|s| (s.x - edge[0]).hypot(s.y - edge[1]) < 2.5 * radius),
+            "There is room for another sample at {:?}",
+            edge
+        );
+    }
+}
+
+#[test]
+fn test_polygon_exact_ebeida_has_no_gaps() {
+    let radius = 0.02;
+    let algo = algorithm::EbeidaConfig::new().with_exact(true);
+    for &ptype in &[Normal, Periodic] {
+        let builder = Builder::with_domain(4.0, 1.0, radius, ptype).with_polygon(room());
+        let samples = builder
+            .clone()
+            .build(SmallRng::from_seed([3; 16]), algo)
+            .generate();
+        for gap in validity::gaps(&samples, &builder) {
+            assert!(gap.distance < 2.0001 * radius, "{:?}", gap);
+        }
+    }
+}
+
+/// Polygon with teeth thinner than the cells of the grid, whose corners all lie outside of it.
+fn comb() -> Polygon {
+    let mut ring = vec![[0.05, 0.05], [0.95, 0.05], [0.95, 0.1]];
+    for k in (0..8).rev() {
+        let x = 0.1 + 0.1 * k as f32;
+        ring.extend(vec![[x + 0.005, 0.1], [x + 0.005, 0.9], [x, 0.9], [x, 0.1]]);
+    }
+    ring.push([0.05, 0.1]);
+    Polygon::new(ring.into_iter().map(Into::into))
+}
+
+#[test]
+fn test_polygon_thin_parts_are_filled() {
+    let radius = 0.01;
+    for &ptype in &[Normal, Periodic] {
+        let builder = Builder::with_radius(radius, ptype).with_polygon(comb());
+        for seed in 0..4 {
+            let samples = builder
+                .clone()
+                .build(SmallRng::from_seed([seed; 16]), algorithm::Ebeida)
+                .generate();
+            for gap in validity::gaps(&samples, &builder) {
+                assert!(gap.distance < 2.2 * radius, "{:?}", gap);
+            }
+            // Regions around the teeth are mostly outside of the polygon, so check along the teeth too.
+            for k in 0..8 {
+                for i in 0..100 {
+                    let point = [
+                        0.1025 + 0.1 * k as f32,
+                        0.1 + 0.8 * (i as f32 + 0.5) / 100.0,
+                    ];
+                    assert!(
+                        samples
+                            .iter()
+                            .any(|s| (s.x - point[0]).hypot(s.y - point[1]) < 2.2 * radius),
+                        "There is room for another sample at {:?}",
+                        point
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use poisson2d::Type::*;
+use poisson2d::{algorithm, validity, Builder, Type};
+use rand::{rngs::SmallRng, SeedableRng};
+
+use crate::helper::test_with_builder;
+
+mod helper;
+
+/// Leaves out a lake in the middle and a strip of shore at the left edge.
+fn dry(p: mint::Vector2<f32>) -> bool {
+    (p.x - 0.5).hypot(p.y - 0.5) > 0.25 && p.x > 0.1
+}
+
+fn check_dry<A>(builder: &Builder, algo: A)
+where
+    A: algorithm::Creator,
+{
+    for seed in 0..3 {
+        let samples = builder
+            .clone()
+            .build(SmallRng::from_seed([seed; 16]), algo)
+            .generate();
+        assert!(!samples.is_empty());
+        assert!(
+            samples.iter().all(|&s| dry(s)),
+            "{:?} sampled rejected points",
+            algo
+        );
+        assert!(validity::violations(&samples, builder).is_empty());
+    }
+}
+
+#[test]
+fn test_predicate_normal() {
+    let builder = Builder::with_samples(100, 0.8, Normal).with_predicate(dry);
+    test_with_builder(&builder, 50);
+}
+
+#[test]
+fn test_predicate_periodic() {
+    let builder = Builder::with_samples(100, 0.8, Periodic).with_predicate(dry);
+    test_with_builder(&builder, 50);
+}
+
+#[test]
+fn test_predicate_rejected_points_stay_empty() {
+    for &ptype in &[Normal, Periodic] {
+        let builder = Builder::with_radius(0.01, ptype).with_predicate(dry);
+        check_dry(&builder, algorithm::Ebeida);
+        check_dry(&builder, algorithm::EbeidaConfig::new().with_exact(true));
+        check_dry(&builder, algorithm::Bridson);
+        check_dry(
+            &builder,
+            algorithm::BridsonConfig::new().with_fill_gaps(true),
+        );
+        // Sample elimination doesn't keep the samples apart, but leaves out the rejected points too.
+        let samples = builder
+            .clone()
+            .build(SmallRng::from_seed([0; 16]), algorithm::SampleElimination)
+            .generate();
+        assert!(samples.iter().all(|&s| dry(s)));
+    }
+}
+
+#[test]
+fn test_predicate_rejecting_everything_generates_nothing() {
+    let builder = Builder::with_radius(0.005, Type::Normal).with_predicate(|_| false);
+    let rand = SmallRng::from_seed([1; 16]);
+    assert!(builder
+        .clone()
+        .build(rand.clone(), algorithm::Ebeida)
+        .generate()
+        .is_empty());
+    let exact = algorithm::EbeidaConfig::new().with_exact(true);
+    assert!(builder
+        .clone()
+        .build(rand.clone(), exact)
+        .generate()
+        .is_empty());
+    assert!(builder
+        .clone()
+        .build(rand.clone(), algorithm::Bridson)
+        .generate()
+        .is_empty());
+    let fill = algorithm::BridsonConfig::new().with_fill_gaps(true);
+    assert!(builder.build(rand, fill).generate().is_empty());
+}
+
+#[test]
+fn test_predicate_maximal_samplings_have_no_gaps() {
+    let radius = 0.01;
+    for &ptype in &[Normal, Periodic] {
+        let builder = Builder::with_radius(radius, ptype).with_predicate(dry);
+        let exact = builder.clone().build(
+            SmallRng::from_seed([3; 16]),
+            algorithm::EbeidaConfig::new().with_exact(true),
+        );
+        let fill = builder.clone().build(
+            SmallRng::from_seed([3; 16]),
+            algorithm::BridsonConfig::new().with_fill_gaps(true),
+        );
+        for samples in &[exact.generate(), fill.generate()] {
+            for gap in validity::gaps(samples, &builder) {
+                assert!(gap.distance < 2.0001 * radius, "{:?}", gap);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_predicate_of_chunks_is_given_world_coordinates() {
+    let builder = Builder::with_domain(0.5, 0.5, 0.02, Normal).with_predicate(dry);
+    let mut world = builder.build_chunked::<SmallRng, _>(7, algorithm::Bridson);
+    for &(x, y) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+        let samples = world.chunk(x, y);
+        assert!(!samples.is_empty());
+        assert!(samples.iter().all(|&s| dry(s)));
+    }
+}
+
+#[test]
+fn test_predicate_rejecting_far_edges_generates_quickly() {
+    // Only the strips right before the far edges are rejected, so the corners on the edges are still accepted.
+    let builder = Builder::with_radius(0.01, Normal)
+        .with_predicate(|p| (p.x < 0.999 || p.x >= 1.0) && (p.y < 0.999 || p.y >= 1.0));
+    for &exact in &[false, true] {
+        let algo = algorithm::EbeidaConfig::new().with_exact(exact);
+        let mut iter = builder
+            .clone()
+            .build(SmallRng::from_seed([5; 16]), algo)
+            .into_iter();
+        let samples = iter.by_ref().collect::<Vec<_>>();
+        assert!(samples.iter().all(|s| s.x < 0.999 && s.y < 0.999));
+        assert!(
+            iter.algorithm().stats().active.iter().all(|&a| a < 20_000),
+            "{:?}",
+            iter.algorithm().stats()
+        );
+    }
+}
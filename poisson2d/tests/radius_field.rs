@@ -0,0 +1,65 @@
+use poisson2d::Type::*;
+use poisson2d::{algorithm, Builder};
+use rand::{rngs::SmallRng, SeedableRng};
+
+use crate::helper::test_with_builder;
+
+mod helper;
+
+#[test]
+fn test_radius_field_gradient_normal() {
+    let builder = Builder::with_radius(0.01, Normal).with_radius_field(0.05, |p| 0.01 + 0.04 * p.x);
+    test_with_builder(&builder, 5);
+}
+
+#[test]
+fn test_radius_field_gradient_periodic() {
+    let builder =
+        Builder::with_radius(0.01, Periodic).with_radius_field(0.05, |p| 0.01 + 0.04 * p.x);
+    test_with_builder(&builder, 5);
+}
+
+#[test]
+fn test_radius_field_clamped() {
+    let builder = Builder::with_radius(0.02, Normal).with_radius_field(0.04, |p| p.y - 0.5);
+    test_with_builder(&builder, 5);
+}
+
+#[test]
+fn test_radius_field_in_domain() {
+    let builder = Builder::with_domain(4.0, 1.0, 0.02, Periodic).with_radius_field(0.1, |p| {
+        if p.x < 2.0 {
+            0.02
+        } else {
+            0.1
+        }
+    });
+    test_with_builder(&builder, 3);
+}
+
+#[test]
+fn test_radius_field_changes_density() {
+    let builder = Builder::with_radius(0.01, Normal).with_radius_field(0.04, |p| 0.01 + 0.03 * p.x);
+    for algo in 0..2 {
+        let rand = SmallRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let samples = if algo == 0 {
+            builder.clone().build(rand, algorithm::Ebeida).generate()
+        } else {
+            builder.clone().build(rand, algorithm::Bridson).generate()
+        };
+        let left = samples.iter().filter(|s| s.x < 0.5).count();
+        let right = samples.len() - left;
+        assert!(
+            left > 2 * right,
+            "{} samples on the left and {} on the right",
+            left,
+            right
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_radius_field_too_small_max_radius() {
+    let _ = Builder::with_radius(0.1, Normal).with_radius_field(0.05, |_| 0.1);
+}
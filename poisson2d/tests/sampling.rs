@@ -0,0 +1,152 @@
+use glam::Vec2;
+use poisson2d::sampling::Sampling;
+use poisson2d::Type::*;
+use poisson2d::{algorithm, Builder, Error, Type};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+fn distance(v1: Vec2, v2: Vec2, dimensions: Vec2, ptype: Type) -> f32 {
+    let mut diff = (v1 - v2).abs();
+    if let Periodic = ptype {
+        for n in 0..2 {
+            diff[n] = diff[n].min(dimensions[n] - diff[n]);
+        }
+    }
+    diff.length()
+}
+
+fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+    v.sort_unstable();
+    v
+}
+
+fn test_queries(builder: Builder) {
+    let dimensions: Vec2 = builder.dimensions().into();
+    let ptype = builder.poisson_type();
+    let mut rng = SmallRng::from_seed([5; 16]);
+    let sampling = builder.build(rng.clone(), algorithm::Ebeida).generate();
+    assert!(!sampling.is_empty());
+    for _ in 0..100 {
+        let point = Vec2::new(
+            rng.gen_range(-0.1, 1.1) * dimensions.x(),
+            rng.gen_range(-0.1, 1.1) * dimensions.y(),
+        );
+        let d = rng.gen_range(0.0, 0.5);
+        let expected = (0..sampling.len())
+            .filter(|&i| distance(sampling[i].into(), point, dimensions, ptype) <= d)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, sorted(sampling.within(point.into(), d)));
+
+        let mut by_distance = (0..sampling.len()).collect::<Vec<_>>();
+        by_distance.sort_by(|&a, &b| {
+            let da = distance(sampling[a].into(), point, dimensions, ptype);
+            let db = distance(sampling[b].into(), point, dimensions, ptype);
+            da.total_cmp(&db)
+        });
+        let k = rng.gen_range(1, 20);
+        assert_eq!(by_distance[..k].to_vec(), sampling.nearest(point.into(), k));
+
+        let size = Vec2::new(rng.gen_range(0.0, 0.6), rng.gen_range(0.0, 0.6)) * dimensions;
+        let max = point + size;
+        let expected = (0..sampling.len())
+            .filter(|&i| {
+                let v: Vec2 = sampling[i].into();
+                (0..2).all(|n| match ptype {
+                    Periodic => (v[n] - point[n]).rem_euclid(dimensions[n]) <= size[n],
+                    Normal => point[n] <= v[n] && v[n] <= max[n],
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(expected, sorted(sampling.in_rect(point.into(), max.into())));
+    }
+}
+
+#[test]
+fn test_sampling_queries_normal() {
+    test_queries(Builder::with_radius(0.02, Normal));
+}
+
+#[test]
+fn test_sampling_queries_periodic() {
+    test_queries(Builder::with_radius(0.02, Periodic));
+}
+
+#[test]
+fn test_sampling_queries_rectangle() {
+    test_queries(Builder::with_domain(3.0, 1.0, 0.03, Periodic));
+    test_queries(Builder::with_domain(1.0, 2.0, 0.03, Normal));
+}
+
+#[test]
+fn test_sampling_periodic_wraps_around() {
+    let builder = Builder::with_radius(0.1, Periodic);
+    let sampling = Sampling::new(
+        &builder,
+        vec![[0.05, 0.5].into(), [0.95, 0.5].into(), [0.5, 0.5].into()],
+    );
+    assert_eq!(vec![0, 1], sorted(sampling.within([0.0, 0.5].into(), 0.06)));
+    assert_eq!(vec![1, 0], sampling.nearest([0.97, 0.5].into(), 2));
+    assert_eq!(
+        vec![0, 1],
+        sorted(sampling.in_rect([0.9, 0.4].into(), [1.1, 0.6].into()))
+    );
+}
+
+#[test]
+fn test_sampling_samples_outside_of_area() {
+    let builder = Builder::with_radius(0.1, Normal);
+    let sampling = Sampling::new(&builder, vec![[-0.5, 0.5].into(), [0.5, 0.5].into()]);
+    assert_eq!(vec![0], sampling.within([-0.4, 0.5].into(), 0.2));
+    assert_eq!(vec![0, 1], sampling.nearest([-0.4, 0.5].into(), 5));
+    assert_eq!(vec![1, 0], sampling.nearest([10.0, 10.0].into(), 2));
+    let builder = Builder::with_radius(0.1, Periodic);
+    let sampling = Sampling::new(&builder, vec![[0.1, 0.5].into(), [0.5, 0.5].into()]);
+    assert_eq!(vec![0, 1], sampling.nearest([10.0, 10.5].into(), 2));
+    assert!(sampling
+        .in_rect([0.6, 0.6].into(), [0.9, 0.9].into())
+        .is_empty());
+}
+
+#[test]
+fn test_sampling_equality() {
+    let builder = Builder::with_samples(100, 0.8, Normal);
+    let generate = |seed| {
+        builder
+            .clone()
+            .build(SmallRng::from_seed([seed; 16]), algorithm::Ebeida)
+            .generate()
+    };
+    assert_eq!(generate(1), generate(1));
+    assert_ne!(generate(1), generate(2));
+    let samples = generate(1).to_vec();
+    let periodic = Builder::with_samples(100, 0.8, Periodic);
+    assert_ne!(
+        Sampling::new(&builder, samples.clone()),
+        Sampling::new(&periodic, samples)
+    );
+}
+
+#[test]
+fn test_sampling_try_zero_radius() {
+    let result = Sampling::try_new(&Builder::default(), vec![[0.5, 0.5].into()]);
+    assert_eq!(
+        Some(Error::InvalidRadius {
+            radius: 0.0,
+            max: 2f32.sqrt() / 2.0
+        }),
+        result.err()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_sampling_zero_radius() {
+    let _ = Sampling::new(&Builder::default(), vec![[0.5, 0.5].into()]);
+}
+
+#[test]
+#[should_panic]
+fn test_generate_zero_radius() {
+    let _ = Builder::default()
+        .build(SmallRng::from_seed([0; 16]), algorithm::Bridson)
+        .generate();
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "serde")]
+
+use poisson2d::algorithm::{self, Creator};
+use poisson2d::multiclass::{ClassIter, Classes};
+use poisson2d::polygon::Polygon;
+use poisson2d::{Builder, PoissonIter, Type};
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn test_resume<A>(builder: Builder, algo: A)
+where
+    A: Creator,
+    A::Algo: Serialize + DeserializeOwned,
+{
+    for seed in 0..5 {
+        let rng = Pcg64Mcg::seed_from_u64(seed);
+        let expected = builder.clone().build(rng.clone(), algo).generate().to_vec();
+        let mut iter = builder.clone().build(rng, algo).into_iter();
+        let mut result = iter.by_ref().take(expected.len() / 2).collect::<Vec<_>>();
+        let saved = serde_json::to_string(&iter).unwrap();
+        drop(iter);
+        let iter: PoissonIter<Pcg64Mcg, A::Algo> = serde_json::from_str(&saved).unwrap();
+        result.extend(iter);
+        assert_eq!(
+            expected, result,
+            "Resuming '{:?}' changed the samples.",
+            algo
+        );
+    }
+}
+
+#[test]
+fn test_resume_normal() {
+    let builder = Builder::with_samples(300, 0.8, Type::Normal);
+    test_resume(builder.clone(), algorithm::Bridson);
+    test_resume(builder.clone(), algorithm::Ebeida);
+    test_resume(builder, algorithm::SampleElimination);
+}
+
+#[test]
+fn test_resume_periodic_domain() {
+    let builder = Builder::with_domain_samples(3.0, 1.0, 300, 0.8, Type::Periodic);
+    test_resume(builder.clone(), algorithm::Bridson);
+    test_resume(builder.clone(), algorithm::Ebeida);
+    test_resume(builder, algorithm::SampleElimination);
+}
+
+#[test]
+fn test_resume_polygon() {
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [0.5, 1.0].into(),
+    ]);
+    let builder = Builder::with_radius(0.03, Type::Normal).with_polygon(polygon);
+    test_resume(builder.clone(), algorithm::Bridson);
+    test_resume(builder, algorithm::Ebeida);
+}
+
+#[test]
+fn test_resume_multiclass() {
+    let classes = Classes::from_radii(&[0.05, 0.02, 0.01]);
+    for seed in 0..5 {
+        let rng = Pcg64Mcg::seed_from_u64(seed);
+        let builder = Builder::with_radius(0.01, Type::Periodic);
+        let expected = builder
+            .clone()
+            .build_with_classes(rng.clone(), classes.clone())
+            .generate();
+        let mut iter = builder.build_with_classes(rng, classes.clone()).into_iter();
+        let mut result = iter.by_ref().take(expected.len() / 2).collect::<Vec<_>>();
+        let saved = serde_json::to_string(&iter).unwrap();
+        drop(iter);
+        let iter: ClassIter<Pcg64Mcg> = serde_json::from_str(&saved).unwrap();
+        assert_eq!(&classes, iter.classes());
+        result.extend(iter);
+        assert_eq!(expected, result, "Resuming changed the samples.");
+    }
+}
+
+#[test]
+fn test_resume_any_algorithm() {
+    let builder = Builder::with_samples(300, 0.8, Type::Periodic);
+    test_resume(
+        builder.clone(),
+        algorithm::AnyAlgorithm::from(algorithm::Ebeida),
+    );
+    test_resume(
+        builder.clone(),
+        algorithm::AnyAlgorithm::from(algorithm::BridsonConfig::new().with_fill_gaps(true)),
+    );
+    test_resume(builder, algorithm::AnyAlgorithm::SampleElimination);
+}
+
+#[test]
+fn test_any_algorithm_from_config() {
+    let saved = r#"{"Bridson":{"candidate_count":10,"candidates":"Roberts","fill_gaps":true}}"#;
+    let algo: algorithm::AnyAlgorithm = serde_json::from_str(saved).unwrap();
+    let expected = algorithm::BridsonConfig::new()
+        .with_candidate_count(10)
+        .with_candidates(algorithm::Candidates::Roberts)
+        .with_fill_gaps(true);
+    assert_eq!(algorithm::AnyAlgorithm::from(expected), algo);
+}
+
+#[test]
+fn test_builder_round_trip() {
+    let polygon = Polygon::new(vec![
+        [0.0, 0.0].into(),
+        [1.0, 0.0].into(),
+        [0.5, 1.0].into(),
+    ]);
+    let builder = Builder::with_domain(2.0, 1.0, 0.05, Type::Periodic).with_polygon(polygon);
+    let saved = serde_json::to_string(&builder).unwrap();
+    assert_eq!(builder, serde_json::from_str(&saved).unwrap());
+}
+
+#[test]
+fn test_radius_field_is_not_serialized() {
+    let builder =
+        Builder::with_radius(0.01, Type::Normal).with_radius_field(0.05, |p| 0.01 + 0.04 * p.x);
+    assert!(serde_json::to_string(&builder).is_err());
+}
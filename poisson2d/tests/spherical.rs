@@ -0,0 +1,149 @@
+use glam::Vec3;
+use poisson2d::spherical::{Builder, Creator};
+use poisson2d::{algorithm, Error};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+fn angle(v1: Vec3, v2: Vec3) -> f32 {
+    v1.cross(v2).length().atan2(v1.dot(v2))
+}
+
+fn test_sphere<A>(builder: Builder, seeds: u8, algo: A) -> Vec<Vec<Vec3>>
+where
+    A: Creator,
+{
+    let min = 2.0 * builder.radius();
+    (0..seeds)
+        .map(|seed| {
+            let mut iter = builder.build(SmallRng::from_seed([seed; 16]), algo).into_iter();
+            let mut samples = vec![];
+            loop {
+                let (low, high) = iter.size_hint();
+                match iter.next() {
+                    Some(s) => {
+                        let s = Vec3::from(s);
+                        assert!((s.length() - 1.0).abs() < 1e-4, "{:?} isn't a unit vector.", s);
+                        assert!(high.unwrap() >= 1);
+                        samples.push(s);
+                    }
+                    None => {
+                        assert_eq!(0, low);
+                        break;
+                    }
+                }
+            }
+            for (i, &v1) in samples.iter().enumerate() {
+                for &v2 in &samples[i + 1..] {
+                    let a = angle(v1, v2);
+                    assert!(
+                        a >= min * 0.9999,
+                        "Samples {:?} and {:?} generated by '{:?}' are only {} apart while {} is required.",
+                        v1,
+                        v2,
+                        algo,
+                        a,
+                        min
+                    );
+                }
+            }
+            samples
+        })
+        .collect()
+}
+
+#[test]
+fn test_sphere_bridson() {
+    test_sphere(Builder::with_radius(0.05), 5, algorithm::Bridson);
+}
+
+#[test]
+fn test_sphere_ebeida() {
+    test_sphere(Builder::with_radius(0.05), 5, algorithm::Ebeida);
+}
+
+#[test]
+fn test_sphere_large_radius() {
+    test_sphere(
+        Builder::with_radius(std::f32::consts::PI / 4.0),
+        10,
+        algorithm::Bridson,
+    );
+    test_sphere(
+        Builder::with_radius(std::f32::consts::PI / 4.0),
+        10,
+        algorithm::Ebeida,
+    );
+}
+
+#[test]
+fn test_sphere_with_samples() {
+    for samples in test_sphere(Builder::with_samples(1000, 0.8), 3, algorithm::Ebeida) {
+        assert!(
+            700 < samples.len() && samples.len() < 1300,
+            "{} samples",
+            samples.len()
+        );
+    }
+}
+
+#[test]
+fn test_sphere_ebeida_is_maximal() {
+    let radius = 0.05;
+    let mut rng = SmallRng::from_seed([42; 16]);
+    for samples in test_sphere(Builder::with_radius(radius), 2, algorithm::Ebeida) {
+        for _ in 0..2000 {
+            let point = Vec3::new(
+                rng.gen_range(-1.0, 1.0),
+                rng.gen_range(-1.0, 1.0),
+                rng.gen_range(-1.0, 1.0),
+            )
+            .normalize();
+            let closest = samples
+                .iter()
+                .map(|&s| angle(s, point))
+                .fold(f32::MAX, f32::min);
+            // The maximality is approximate in the same way as on the plane.
+            assert!(
+                closest < 2.2 * radius,
+                "{:?} is {} away from closest sample.",
+                point,
+                closest
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sphere_restrict() {
+    let builder = Builder::with_radius(0.1);
+    let mut iter = builder
+        .build(SmallRng::from_seed([1; 16]), algorithm::Ebeida)
+        .into_iter();
+    let north = Vec3::new(0.0, 0.0, 2.0);
+    assert!(iter.stays_legal(north.into()));
+    iter.restrict(north.into());
+    assert!(!iter.stays_legal(Vec3::new(0.0, 0.1, 1.0).into()));
+    for s in iter {
+        assert!(angle(Vec3::from(s), north) >= 0.2 * 0.9999);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sphere_too_large_radius() {
+    let _ = Builder::with_radius(1.0);
+}
+
+#[test]
+fn test_sphere_try_invalid_values() {
+    let max = std::f32::consts::PI / 4.0;
+    assert_eq!(
+        Err(Error::InvalidRadius { radius: 1.0, max }),
+        Builder::try_with_radius(1.0)
+    );
+    assert_eq!(Err(Error::ZeroSamples), Builder::try_with_samples(0, 0.8));
+    assert_eq!(
+        Err(Error::InvalidRelativeRadius(1.5)),
+        Builder::try_with_samples(100, 1.5)
+    );
+    assert_eq!(Ok(Builder::with_radius(max)), Builder::try_with_radius(max));
+}
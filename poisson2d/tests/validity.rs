@@ -1,5 +1,6 @@
 use glam::Vec2;
-use poisson2d::Type;
+use poisson2d::validity::{self, Gap, Violation};
+use poisson2d::{algorithm, Builder, Type};
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use rand_distr::StandardNormal;
 
@@ -8,6 +9,7 @@ use crate::helper::When::*;
 mod helper;
 
 #[test]
+#[allow(clippy::redundant_pattern_matching)]
 fn multiple_too_close_invalid() {
     let samples = 100;
     let relative_radius = 0.8;
@@ -48,3 +50,172 @@ pub fn sphere_uniform_point<R: Rng>(rng: &mut R) -> Vec2 {
     }
     result.normalize()
 }
+
+fn generate(builder: &Builder, seed: u8) -> Vec<mint::Vector2<f32>> {
+    builder
+        .clone()
+        .build(SmallRng::from_seed([seed; 16]), algorithm::Ebeida)
+        .generate()
+        .to_vec()
+}
+
+#[test]
+fn generated_samples_have_no_violations() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_domain(2.0, 1.0, 0.03, ptype);
+        for seed in 0..3 {
+            assert_eq!(
+                vec![] as Vec<Violation>,
+                validity::violations(&generate(&builder, seed), &builder)
+            );
+        }
+    }
+}
+
+#[test]
+fn violations_are_reported() {
+    let builder = Builder::with_radius(0.1, Type::Periodic);
+    let samples = vec![
+        [0.01, 0.5].into(),
+        [0.5, 0.5].into(),
+        [0.95, 0.5].into(),
+        [0.6, 0.6].into(),
+    ];
+    let violations = validity::violations(&samples, &builder);
+    assert_eq!(2, violations.len());
+    assert_eq!((0, 2), (violations[0].first, violations[0].second));
+    assert!((violations[0].distance - 0.06).abs() < 1e-5);
+    assert_eq!(0.2, violations[0].required);
+    assert_eq!((1, 3), (violations[1].first, violations[1].second));
+}
+
+#[test]
+fn violations_use_radius_field() {
+    let builder = Builder::with_radius(0.01, Type::Normal).with_radius_field(0.1, |p| {
+        if p.x < 0.5 {
+            0.1
+        } else {
+            0.01
+        }
+    });
+    let samples = vec![
+        [0.1, 0.5].into(),
+        [0.25, 0.5].into(),
+        [0.6, 0.5].into(),
+        [0.63, 0.5].into(),
+    ];
+    let violations = validity::violations(&samples, &builder);
+    assert_eq!(1, violations.len());
+    assert_eq!((0, 1), (violations[0].first, violations[0].second));
+}
+
+#[test]
+fn ebeida_has_only_small_gaps() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_domain(1.0, 2.0, 0.03, ptype);
+        for seed in 0..3 {
+            for gap in validity::gaps(&generate(&builder, seed), &builder) {
+                // The maximality of the algorithm is approximate.
+                assert!(gap.distance < 2.5 * 0.03, "{:?}", gap);
+            }
+        }
+    }
+}
+
+#[test]
+fn exact_ebeida_has_no_gaps() {
+    let algo = algorithm::EbeidaConfig::new().with_exact(true);
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_domain(1.0, 2.0, 0.03, ptype);
+        for seed in 0..3 {
+            let samples = builder
+                .clone()
+                .build(SmallRng::from_seed([seed; 16]), algo)
+                .generate()
+                .to_vec();
+            assert_eq!(
+                vec![] as Vec<Violation>,
+                validity::violations(&samples, &builder)
+            );
+            for gap in validity::gaps(&samples, &builder) {
+                // Only gaps narrower than the precision of the samples can remain.
+                assert!(gap.distance < 2.0001 * 0.03, "{:?}", gap);
+            }
+        }
+    }
+}
+
+#[test]
+fn exact_ebeida_fills_around_restricted_samples() {
+    let algo = algorithm::EbeidaConfig::new().with_exact(true);
+    let builder = Builder::with_domain(1.0, 1.0, 0.02, Type::Normal);
+    let restricted = vec![[0.5, 0.5].into(), [0.515, 0.5].into(), [0.3, 0.7].into()];
+    let mut iter = builder
+        .clone()
+        .build(SmallRng::from_seed([5; 16]), algo)
+        .into_iter();
+    for &sample in &restricted {
+        iter.restrict(sample);
+    }
+    let mut samples = iter.collect::<Vec<_>>();
+    samples.extend(restricted);
+    // Only the restricted samples are too close to each other.
+    assert_eq!(1, validity::violations(&samples, &builder).len());
+    for gap in validity::gaps(&samples, &builder) {
+        assert!(gap.distance < 2.0001 * 0.02, "{:?}", gap);
+    }
+}
+
+#[test]
+fn removed_samples_leave_gaps() {
+    for &ptype in &[Type::Normal, Type::Periodic] {
+        let builder = Builder::with_radius(0.02, ptype);
+        let mut samples = generate(&builder, 7);
+        let before = validity::gaps(&samples, &builder);
+        let center = Vec2::new(0.5, 0.5);
+        samples.retain(|&s| (Vec2::from(s) - center).length() > 0.1);
+        let gaps = validity::gaps(&samples, &builder);
+        assert!(
+            (Vec2::from(gaps[0].position) - center).length() < 0.05,
+            "{:?}",
+            gaps[0]
+        );
+        assert!(gaps[0].distance > 0.1);
+        // Gaps away from the removed samples stay the same.
+        let away = |gaps: &[Gap]| {
+            gaps.iter()
+                .filter(|g| (Vec2::from(g.position) - center).length() > 0.2)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(away(&before), away(&gaps[1..]));
+    }
+}
+
+#[test]
+fn gaps_wrap_around_periodic_edges() {
+    let builder = Builder::with_radius(0.02, Type::Periodic);
+    let mut samples = generate(&builder, 3);
+    let corner = |s: Vec2| (0..2).all(|n| s[n].min(1.0 - s[n]) < 0.1);
+    samples.retain(|&s| !corner(s.into()));
+    let gaps = validity::gaps(&samples, &builder);
+    assert!(corner(gaps[0].position.into()), "{:?}", gaps[0]);
+    assert!(gaps[0].distance > 0.08);
+    assert!(gaps.len() == 1 || gaps[1].distance < 2.5 * 0.02);
+
+    let builder = Builder::with_radius(0.02, Type::Normal);
+    let samples = generate(&builder, 3)
+        .into_iter()
+        .filter(|&s| !corner(s.into()))
+        .collect::<Vec<_>>();
+    let gaps = validity::gaps(&samples, &builder);
+    assert_eq!(4, gaps.iter().filter(|g| g.distance > 0.08).count());
+}
+
+#[test]
+fn empty_sampling_is_one_gap() {
+    let builder = Builder::with_domain(2.0, 1.0, 0.1, Type::Normal);
+    let gaps = validity::gaps(&[], &builder);
+    assert_eq!(1, gaps.len());
+    assert_eq!(Vec2::new(1.0, 0.5), gaps[0].position.into());
+}